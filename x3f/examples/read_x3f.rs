@@ -16,7 +16,7 @@ fn main() {
     let x3f = match X3F::from_bytes(&data) {
         Ok(x3f) => x3f,
         Err(e) => {
-            eprintln!("Failed to parse X3F: {:?}", e);
+            eprintln!("Failed to parse X3F: {e:?}");
             std::process::exit(1);
         },
     };
@@ -28,11 +28,11 @@ fn main() {
         "File type: {:?}",
         String::from_utf8_lossy(header.file_type_identifier())
     );
-    println!("Version: {:?}", header.file_format_version());
+    println!("Version: {}", header.version());
     println!(
         "Image size: {}x{}",
-        u32::from_le_bytes(header.image_columns().try_into().unwrap()),
-        u32::from_le_bytes(header.image_rows().try_into().unwrap())
+        u32::from_le_bytes(header.image_columns().try_into().unwrap_or([0u8; 4])),
+        u32::from_le_bytes(header.image_rows().try_into().unwrap_or([0u8; 4]))
     );
     println!("Rotation: {:?}", header.rotation());
 
@@ -47,33 +47,41 @@ fn main() {
 
     // ディレクトリ情報
     let dir = x3f.directory();
-    let num_entries = u32::from_le_bytes(dir.entry_count().try_into().unwrap());
+    let num_entries = u32::from_le_bytes(dir.entry_count().try_into().unwrap_or([0u8; 4]));
     println!("\n=== Directory ===");
-    println!("Number of entries: {}", num_entries);
+    println!("Number of entries: {num_entries}");
 
     // 各エントリの情報
     println!("\n=== Directory Entries ===");
     for (i, entry) in dir.entries().enumerate() {
-        let offset = u32::from_le_bytes(entry.data_offset().try_into().unwrap());
-        let length = u32::from_le_bytes(entry.data_length().try_into().unwrap());
+        let offset = entry.data_offset_u32();
+        let length = entry.data_length_u32();
         let entry_type = String::from_utf8_lossy(entry.entry_type());
-        println!(
-            "[{}] Type: {}, Offset: {}, Length: {}",
-            i, entry_type, offset, length
-        );
+        println!("[{i}] Type: {entry_type}, Offset: {offset}, Length: {length}");
     }
 
     // セクションデータの詳細
     println!("\n=== Section Data ===");
-    for entry in dir.entries() {
-        let entry_type = String::from_utf8_lossy(entry.entry_type());
-        match x3f.section_data(&entry) {
+    for (i, entry, data) in x3f.indexed_sections() {
+        let tag = entry.entry_type_fourcc();
+        match data {
             Some(section) => {
-                println!("Section {}: {:?}", entry_type, section);
+                println!("[{i}] Section {tag}: {section:?}");
             },
             None => {
-                println!("Section {}: (out of bounds)", entry_type);
+                println!("[{i}] Section {tag}: (not decoded)");
             },
         }
     }
+
+    // 画像セクション
+    println!("\n=== Images ===");
+    for image in x3f.images() {
+        println!(
+            "{}x{}, {}",
+            image.image_columns_u32(),
+            image.image_rows_u32(),
+            image.data_format_enum().description()
+        );
+    }
 }
@@ -0,0 +1,395 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{DirectoryRef, ExtendedHeaderRef, HeaderRef, Prop, Rotation, Version, X3F, X3FError};
+
+/// Assembles an X3F file from header fields and sections.
+///
+/// Sections are laid out in the order they were added, each starting on a 4-byte
+/// boundary, followed by the directory and its pointer. Properties added with
+/// [`Self::add_property`] are encoded into a single `PROP` section placed before all
+/// other sections. An extended header of zeros is written when the version requires one.
+#[derive(Debug, Clone)]
+pub struct X3FBuilder {
+    version: Version,
+    unique_identifier: [u8; 16],
+    columns: u32,
+    rows: u32,
+    rotation: Rotation,
+    properties: Vec<(String, String)>,
+    sections: Vec<([u8; 4], Vec<u8>)>,
+}
+
+impl Default for X3FBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl X3FBuilder {
+    /// Creates a builder for a version 2.2 file with no sections.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            version: Version::new(2, 2),
+            unique_identifier: [0; 16],
+            columns: 0,
+            rows: 0,
+            rotation: Rotation::Deg0,
+            properties: Vec::new(),
+            sections: Vec::new(),
+        }
+    }
+
+    pub fn version(
+        &mut self,
+        version: Version,
+    ) -> &mut Self {
+        self.version = version;
+        self
+    }
+
+    pub fn unique_identifier(
+        &mut self,
+        unique_identifier: [u8; 16],
+    ) -> &mut Self {
+        self.unique_identifier = unique_identifier;
+        self
+    }
+
+    pub fn dimensions(
+        &mut self,
+        columns: u32,
+        rows: u32,
+    ) -> &mut Self {
+        self.columns = columns;
+        self.rows = rows;
+        self
+    }
+
+    pub fn rotation(
+        &mut self,
+        rotation: Rotation,
+    ) -> &mut Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Appends a section of type `tag` holding `data`, which must already start with the
+    /// section identifier its type requires.
+    pub fn add_section(
+        &mut self,
+        tag: [u8; 4],
+        data: &[u8],
+    ) -> &mut Self {
+        self.sections.push((tag, data.to_vec()));
+        self
+    }
+
+    /// Appends a name/value pair to the `PROP` section written by [`Self::build`].
+    pub fn add_property(
+        &mut self,
+        name: &str,
+        value: &str,
+    ) -> &mut Self {
+        self.properties
+            .push((String::from(name), String::from(value)));
+        self
+    }
+
+    /// Lays out the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`X3FError::OutOfBounds`] if an offset, length or count does not fit in
+    /// its 32-bit field.
+    pub fn build(&self) -> Result<Vec<u8>, X3FError> {
+        let mut header = [0u8; HeaderRef::LENGTH];
+        header[0..4].copy_from_slice(b"FOVb");
+        header[4..8].copy_from_slice(&self.version.raw().to_le_bytes());
+        header[8..24].copy_from_slice(&self.unique_identifier);
+        header[28..32].copy_from_slice(&self.columns.to_le_bytes());
+        header[32..36].copy_from_slice(&self.rows.to_le_bytes());
+        header[36..40].copy_from_slice(&self.rotation.as_u32().to_le_bytes());
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&header);
+        if HeaderRef::from_array(&header).requires_extended_header() {
+            bytes.extend_from_slice(&[0; ExtendedHeaderRef::LENGTH]);
+        }
+
+        let prop = (!self.properties.is_empty())
+            .then(|| encode_prop(&self.properties))
+            .transpose()?;
+        let sections = prop
+            .as_deref()
+            .map(|prop| (*b"PROP", prop))
+            .into_iter()
+            .chain(
+                self.sections
+                    .iter()
+                    .map(|(tag, data)| (*tag, data.as_slice())),
+            );
+
+        let mut entries = Vec::new();
+        for (tag, data) in sections {
+            entries.push((to_u32(bytes.len())?, to_u32(data.len())?, tag));
+            bytes.extend_from_slice(data);
+            bytes.resize(bytes.len().next_multiple_of(4), 0);
+        }
+
+        let directory_offset = to_u32(bytes.len())?;
+        bytes.extend_from_slice(&DirectoryRef::SECTION_IDENTIFIER);
+        bytes.extend_from_slice(&Version::new(2, 0).raw().to_le_bytes());
+        bytes.extend_from_slice(&to_u32(entries.len())?.to_le_bytes());
+        for (offset, length, tag) in entries {
+            bytes.extend_from_slice(&offset.to_le_bytes());
+            bytes.extend_from_slice(&length.to_le_bytes());
+            bytes.extend_from_slice(&tag);
+        }
+        bytes.extend_from_slice(&directory_offset.to_le_bytes());
+        Ok(bytes)
+    }
+
+    /// Returns a copy of `existing` with a section of type `tag` holding `data` appended.
+    ///
+    /// The new section starts on a 4-byte boundary after the existing sections, followed by
+    /// a directory listing the existing entries and then the new one. Existing sections keep
+    /// their offsets. If a section lies after the old directory, the old directory is kept
+    /// in place as unused bytes; otherwise it is dropped, along with any bytes between its
+    /// entries and the old pointer.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from [`X3F::from_bytes`] if `existing` does not parse, and
+    /// [`X3FError::OutOfBounds`] if an existing entry's data does not end before the old
+    /// directory pointer, or if an offset, length or count does not fit in its 32-bit field.
+    pub fn append_to(
+        existing: &[u8],
+        tag: [u8; 4],
+        data: &[u8],
+    ) -> Result<Vec<u8>, X3FError> {
+        let x3f = X3F::from_bytes(existing)?;
+        let directory = x3f.directory();
+        let mut kept = x3f.directory_offset;
+        for entry in directory.entries() {
+            // Copied entries keep their offsets, so each must point into the kept bytes
+            // rather than at what replaces the old directory and pointer.
+            match entry.data_range() {
+                Some(range) if range.end <= x3f.directory_pointer_offset => {
+                    kept = kept.max(range.end);
+                },
+                _ => return Err(X3FError::OutOfBounds),
+            }
+        }
+
+        let mut bytes = existing[..kept].to_vec();
+        bytes.resize(bytes.len().next_multiple_of(4), 0);
+        let section_offset = to_u32(bytes.len())?;
+        bytes.extend_from_slice(data);
+        bytes.resize(bytes.len().next_multiple_of(4), 0);
+
+        let directory_offset = to_u32(bytes.len())?;
+        bytes.extend_from_slice(&DirectoryRef::SECTION_IDENTIFIER);
+        bytes.extend_from_slice(directory.section_version());
+        bytes.extend_from_slice(&to_u32(directory.entries().count() + 1)?.to_le_bytes());
+        for entry in directory.entries() {
+            bytes.extend_from_slice(entry.as_bytes());
+        }
+        bytes.extend_from_slice(&section_offset.to_le_bytes());
+        bytes.extend_from_slice(&to_u32(data.len())?.to_le_bytes());
+        bytes.extend_from_slice(&tag);
+        bytes.extend_from_slice(&directory_offset.to_le_bytes());
+        Ok(bytes)
+    }
+}
+
+/// Encodes a CHAR16 property list section holding `pairs` in order.
+pub(crate) fn encode_prop<N: AsRef<str>, V: AsRef<str>>(
+    pairs: &[(N, V)]
+) -> Result<Vec<u8>, X3FError> {
+    let mut table = Vec::new();
+    let mut chars: Vec<u16> = Vec::new();
+    for (name, value) in pairs {
+        for text in [name.as_ref(), value.as_ref()] {
+            table.extend_from_slice(&to_u32(chars.len())?.to_le_bytes());
+            chars.extend(text.encode_utf16());
+            chars.push(0);
+        }
+    }
+
+    let mut bytes = Vec::with_capacity(Prop::LENGTH + table.len() + chars.len() * 2);
+    bytes.extend_from_slice(&Prop::SECTION_IDENTIFIER);
+    bytes.extend_from_slice(&Version::new(2, 0).raw().to_le_bytes());
+    bytes.extend_from_slice(&to_u32(pairs.len())?.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&to_u32(chars.len())?.to_le_bytes());
+    bytes.extend_from_slice(&table);
+    for unit in chars {
+        bytes.extend_from_slice(&unit.to_le_bytes());
+    }
+    Ok(bytes)
+}
+
+impl Prop<'_> {
+    /// Re-encodes the decoded entries as a property list section, keeping this section's
+    /// format version.
+    ///
+    /// A conforming section laid out like [`X3FBuilder`] output re-encodes byte for byte.
+    /// Others may not: shared or unordered string offsets, unpaired surrogates, reserved
+    /// fields, non-CHAR16 formats and trailing bytes are all normalized. Returns an empty
+    /// vector if the result would not fit in 32-bit fields.
+    #[must_use]
+    pub fn reencode(&self) -> Vec<u8> {
+        let pairs: Vec<(String, String)> = self
+            .entries()
+            .map(|(name, value)| (name.collect(), value.collect()))
+            .collect();
+        let Ok(mut bytes) = encode_prop(&pairs) else {
+            return Vec::new();
+        };
+        bytes[4..8].copy_from_slice(self.property_list_format_version());
+        bytes
+    }
+}
+
+fn to_u32(value: usize) -> Result<u32, X3FError> {
+    u32::try_from(value).map_err(|_| X3FError::OutOfBounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DirectoryPointerRef;
+    use crate::test_util::make_prop;
+
+    #[test]
+    fn build_round_trips_properties() {
+        let bytes = X3FBuilder::new()
+            .dimensions(5424, 3616)
+            .rotation(Rotation::Deg90)
+            .add_property("CAMMODEL", "SIGMA dp2 Quattro")
+            .add_property("ISO", "100")
+            .add_section(*b"CAMF", b"SECcdata")
+            .build()
+            .expect("fits in 32-bit fields");
+
+        let x3f = X3F::from_bytes_strict(&bytes).expect("valid X3F");
+        assert_eq!(x3f.header().image_columns_u32(), 5424);
+        assert_eq!(x3f.effective_rotation(), Rotation::Deg90);
+        assert!(x3f.extended_header().is_some());
+        assert!(x3f.check_alignment().next().is_none());
+
+        let prop = x3f.prop().expect("PROP section");
+        assert!(prop.is_conformant());
+        assert!(prop.char_data_matches_declared());
+        let pairs: Vec<(String, String)> = prop
+            .entries()
+            .map(|(name, value)| (name.collect(), value.collect()))
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                (String::from("CAMMODEL"), String::from("SIGMA dp2 Quattro")),
+                (String::from("ISO"), String::from("100")),
+            ]
+        );
+        let tags: Vec<[u8; 4]> = x3f
+            .directory()
+            .entries()
+            .map(|entry| *entry.entry_type_fourcc().as_bytes())
+            .collect();
+        assert_eq!(tags, [*b"PROP", *b"CAMF"]);
+    }
+
+    #[test]
+    fn build_omits_extended_header_for_version_2_0() {
+        let bytes = X3FBuilder::new()
+            .version(Version::new(2, 0))
+            .build()
+            .expect("fits in 32-bit fields");
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        assert!(x3f.extended_header().is_none());
+        assert_eq!(x3f.data_start_offset(), HeaderRef::LENGTH);
+    }
+
+    #[test]
+    fn append_to_adds_section_after_existing_ones() {
+        let existing = X3FBuilder::new()
+            .add_property("ISO", "100")
+            .add_section(*b"CAMF", b"SECcabc")
+            .build()
+            .expect("fits in 32-bit fields");
+
+        let bytes =
+            X3FBuilder::append_to(&existing, *b"XYZW", b"vendor").expect("existing file parses");
+        let x3f = X3F::from_bytes_strict(&bytes).expect("valid X3F");
+        assert!(x3f.check_alignment().next().is_none());
+        assert!(x3f.prop().is_some());
+
+        let sections: Vec<([u8; 4], Option<&[u8]>)> = x3f
+            .directory()
+            .entries()
+            .map(|entry| {
+                (
+                    *entry.entry_type_fourcc().as_bytes(),
+                    x3f.section_bytes(&entry),
+                )
+            })
+            .collect();
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[1], (*b"CAMF", Some(&b"SECcabc"[..])));
+        assert_eq!(sections[2], (*b"XYZW", Some(&b"vendor"[..])));
+    }
+
+    #[test]
+    fn append_to_rejects_out_of_bounds_entries() {
+        let mut existing = X3FBuilder::new()
+            .add_section(*b"CAMF", b"SECcabc")
+            .build()
+            .expect("fits in 32-bit fields");
+        let data_length = existing.len() - DirectoryPointerRef::LENGTH - 8;
+        existing[data_length..data_length + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(X3F::from_bytes(&existing).is_ok());
+
+        match X3FBuilder::append_to(&existing, *b"XYZW", b"vendor").unwrap_err() {
+            X3FError::OutOfBounds => {},
+            other => panic!("expected OutOfBounds, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn prop_reencode_round_trips_conforming_sections() {
+        let mut bytes = make_prop(&[("CAMMODEL", "dp2 \u{00C9}"), ("ISO", "100")]);
+        bytes[4..8].copy_from_slice(&Version::new(2, 1).raw().to_le_bytes());
+        let prop = Prop::from_bytes(&bytes).expect("valid prop");
+        let reencoded = prop.reencode();
+        assert_eq!(reencoded, bytes);
+        assert_eq!(
+            Prop::from_bytes(&reencoded).expect("valid prop").reencode(),
+            bytes
+        );
+    }
+
+    #[test]
+    fn prop_reencode_normalizes_shared_offsets() {
+        let mut bytes = make_prop(&[("ISO", "ISO")]);
+        // Point the value at the name's characters.
+        bytes[28..32].copy_from_slice(&0u32.to_le_bytes());
+        let prop = Prop::from_bytes(&bytes).expect("valid prop");
+        let reencoded = prop.reencode();
+        assert_ne!(reencoded, bytes);
+        assert_eq!(reencoded, make_prop(&[("ISO", "ISO")]));
+    }
+
+    #[test]
+    fn encode_prop_matches_fixture_layout() {
+        let pairs = [("ISO", "100"), ("CAMMANUF", "SIGMA")];
+        assert_eq!(
+            encode_prop(&pairs).expect("fits in 32-bit fields"),
+            make_prop(&pairs)
+        );
+    }
+}
@@ -0,0 +1,53 @@
+/// Byte order of multi-byte fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteOrder {
+    LittleEndian,
+}
+
+/// Every multi-byte field in an X3F file is little-endian.
+pub const BYTE_ORDER: ByteOrder = ByteOrder::LittleEndian;
+
+/// Reads a little-endian `u16` at `offset`, or `None` if it runs past the end of `bytes`.
+///
+/// Useful for decoding sections this crate does not interpret, such as CAMF blocks.
+#[must_use]
+pub fn read_u16_le(
+    bytes: &[u8],
+    offset: usize,
+) -> Option<u16> {
+    let value = bytes.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_le_bytes(value.try_into().ok()?))
+}
+
+/// Reads a little-endian `u32` at `offset`, or `None` if it runs past the end of `bytes`.
+#[must_use]
+pub fn read_u32_le(
+    bytes: &[u8],
+    offset: usize,
+) -> Option<u32> {
+    let value = bytes.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_le_bytes(value.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_are_little_endian() {
+        let bytes = [0x01, 0x02, 0x03, 0x04, 0x05];
+        assert_eq!(read_u16_le(&bytes, 0), Some(0x0201));
+        assert_eq!(read_u16_le(&bytes, 3), Some(0x0504));
+        assert_eq!(read_u32_le(&bytes, 0), Some(0x0403_0201));
+        assert_eq!(read_u32_le(&bytes, 1), Some(0x0504_0302));
+    }
+
+    #[test]
+    fn reads_past_end_return_none() {
+        let bytes = [0u8; 4];
+        assert_eq!(read_u16_le(&bytes, 3), None);
+        assert_eq!(read_u16_le(&bytes, usize::MAX), None);
+        assert_eq!(read_u32_le(&bytes, 1), None);
+        assert_eq!(read_u32_le(&bytes, usize::MAX), None);
+    }
+}
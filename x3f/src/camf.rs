@@ -0,0 +1,395 @@
+use core::fmt;
+use core::iter::FusedIterator;
+
+use crate::byte_order::{read_u16_le, read_u32_le};
+use crate::debug_helper::TruncatedBytes;
+use crate::reader::SectionReader;
+
+/// Iterator over the `"CMb?"` blocks of decoded CAMF data.
+///
+/// Iteration stops at the first position that does not hold a well-formed block.
+/// Encrypted or compressed CAMF data must be decoded first; see [`crate::Camf::blocks`].
+#[derive(Clone)]
+pub struct CamfBlocks<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl fmt::Debug for CamfBlocks<'_> {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("CamfBlocks")
+            .field("bytes", &TruncatedBytes(self.bytes))
+            .field("pos", &self.pos)
+            .finish()
+    }
+}
+
+impl<'a> CamfBlocks<'a> {
+    #[must_use]
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+}
+
+impl<'a> Iterator for CamfBlocks<'a> {
+    type Item = CamfBlock<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.bytes.get(self.pos..)?;
+        let block = rest
+            .get(..CamfBlock::LENGTH)
+            .filter(|header| header.starts_with(b"CMb"))
+            .and_then(|header| usize::try_from(read_u32_le(header, 8)?).ok())
+            .filter(|&size| size >= CamfBlock::LENGTH)
+            .and_then(|size| rest.get(..size))
+            .map(|bytes| CamfBlock { bytes });
+
+        if let Some(block) = &block {
+            self.pos += block.bytes.len();
+        } else {
+            self.pos = self.bytes.len();
+        }
+        block
+    }
+}
+
+impl FusedIterator for CamfBlocks<'_> {}
+
+/// # Structure
+///
+/// | Offset | Length | Item | Notes |
+/// | --- | --- | --- | --- |
+/// | 0 | 4 | Block identifier | `"CMbT"` text, `"CMbP"` property list, `"CMbM"` matrix |
+/// | 4 | 4 | Block version |  |
+/// | 8 | 4 | Block size | Including this header |
+/// | 12 | 4 | Name offset | From the start of the block; NUL-terminated ASCII |
+/// | 16 | 4 | Value offset | From the start of the block |
+pub struct CamfBlock<'a> {
+    bytes: &'a [u8],
+}
+
+impl fmt::Debug for CamfBlock<'_> {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("CamfBlock")
+            .field("bytes", &TruncatedBytes(self.bytes))
+            .finish()
+    }
+}
+
+impl<'a> CamfBlock<'a> {
+    pub const LENGTH: usize = 20;
+
+    #[must_use]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    #[must_use]
+    pub fn block_identifier(&self) -> &'a [u8] {
+        &self.bytes[0..4]
+    }
+
+    /// Returns the last byte of the block identifier, e.g. `b'M'` for a matrix.
+    #[must_use]
+    pub fn kind(&self) -> u8 {
+        self.bytes[3]
+    }
+
+    #[must_use]
+    pub fn block_version(&self) -> &'a [u8] {
+        &self.bytes[4..8]
+    }
+
+    /// Returns the block name without its NUL terminator, or `None` if the offset is out of range.
+    #[must_use]
+    pub fn name(&self) -> Option<&'a [u8]> {
+        let start = usize::try_from(read_u32_le(self.bytes, 12)?).ok()?;
+        let rest = self.bytes.get(start..)?;
+        let end = rest
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(rest.len());
+        Some(&rest[..end])
+    }
+
+    /// Returns the bytes from the value offset to the end of the block.
+    #[must_use]
+    pub fn value(&self) -> Option<&'a [u8]> {
+        let start = usize::try_from(read_u32_le(self.bytes, 16)?).ok()?;
+        self.bytes.get(start..)
+    }
+
+    /// Decodes a `"CMbM"` block. Returns `None` for other blocks, malformed matrices,
+    /// or unrecognized element types.
+    #[must_use]
+    pub fn matrix(&self) -> Option<CamfMatrix<'a>> {
+        if self.block_identifier() != b"CMbM" {
+            return None;
+        }
+
+        let mut value = SectionReader::new(self.value()?);
+        let element_type = CamfElementType::from_u32(value.read_u32_le()?)?;
+        let dimension_count = usize::try_from(value.read_u32_le()?).ok()?;
+        let data_offset = usize::try_from(value.read_u32_le()?).ok()?;
+
+        let dimensions = value.read_bytes(dimension_count.checked_mul(12)?)?;
+        let element_count = dimensions
+            .chunks_exact(12)
+            .try_fold(1usize, |count, dimension| {
+                count.checked_mul(usize::try_from(read_u32_le(dimension, 0)?).ok()?)
+            })?;
+        let data_length = element_count.checked_mul(element_type.size())?;
+        let data = self
+            .bytes
+            .get(data_offset..data_offset.checked_add(data_length)?)?;
+
+        Some(CamfMatrix {
+            element_type,
+            dimensions,
+            data,
+        })
+    }
+}
+
+/// Element type of a CAMF matrix, as stored in the first word of its value.
+///
+/// Codes follow community reverse engineering: `0` = i16, `1` and `2` = u32,
+/// `3` = f32, `5` = u8, `6` = u16.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CamfElementType {
+    I16,
+    U8,
+    U16,
+    U32,
+    F32,
+}
+
+impl CamfElementType {
+    #[must_use]
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::I16),
+            1 | 2 => Some(Self::U32),
+            3 => Some(Self::F32),
+            5 => Some(Self::U8),
+            6 => Some(Self::U16),
+            _ => None,
+        }
+    }
+
+    /// Size of one element in bytes.
+    #[must_use]
+    pub const fn size(self) -> usize {
+        match self {
+            Self::U8 => 1,
+            Self::I16 | Self::U16 => 2,
+            Self::U32 | Self::F32 => 4,
+        }
+    }
+}
+
+/// Single decoded CAMF matrix element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CamfValue {
+    I16(i16),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    F32(f32),
+}
+
+/// Decoded `"CMbM"` matrix block.
+pub struct CamfMatrix<'a> {
+    element_type: CamfElementType,
+    dimensions: &'a [u8],
+    data: &'a [u8],
+}
+
+impl fmt::Debug for CamfMatrix<'_> {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("CamfMatrix")
+            .field("element_type", &self.element_type)
+            .field("dimensions", &TruncatedBytes(self.dimensions))
+            .field("data", &TruncatedBytes(self.data))
+            .finish()
+    }
+}
+
+impl<'a> CamfMatrix<'a> {
+    #[must_use]
+    pub fn element_type(&self) -> CamfElementType {
+        self.element_type
+    }
+
+    /// Iterates over the size of each dimension, outermost first.
+    pub fn shape(&self) -> impl Iterator<Item = u32> + use<'a> {
+        self.dimensions
+            .chunks_exact(12)
+            .map(|dimension| read_u32_le(dimension, 0).unwrap_or(0))
+    }
+
+    /// Total number of elements, i.e. the product of [`Self::shape`].
+    #[must_use]
+    pub fn element_count(&self) -> usize {
+        self.data.len() / self.element_type.size()
+    }
+
+    /// Iterates over the elements in storage order.
+    pub fn elements(&self) -> impl Iterator<Item = CamfValue> + use<'a> {
+        let element_type = self.element_type;
+        self.data
+            .chunks_exact(element_type.size())
+            .filter_map(move |element| match element_type {
+                CamfElementType::I16 => {
+                    read_u16_le(element, 0).map(|v| CamfValue::I16(v.cast_signed()))
+                },
+                CamfElementType::U8 => element.first().copied().map(CamfValue::U8),
+                CamfElementType::U16 => read_u16_le(element, 0).map(CamfValue::U16),
+                CamfElementType::U32 => read_u32_le(element, 0).map(CamfValue::U32),
+                CamfElementType::F32 => {
+                    read_u32_le(element, 0).map(|v| CamfValue::F32(f32::from_bits(v)))
+                },
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::test_util::le_u32;
+    use std::vec::Vec;
+
+    fn make_block(
+        identifier: &[u8],
+        name: &[u8],
+        value: &[u8],
+    ) -> Vec<u8> {
+        let name_offset = CamfBlock::LENGTH;
+        let value_offset = name_offset + name.len() + 1;
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(identifier);
+        bytes.extend_from_slice(&0x0002_0000u32.to_le_bytes());
+        bytes.extend_from_slice(&le_u32(value_offset + value.len()));
+        bytes.extend_from_slice(&le_u32(name_offset));
+        bytes.extend_from_slice(&le_u32(value_offset));
+        bytes.extend_from_slice(name);
+        bytes.push(0);
+        bytes.extend_from_slice(value);
+        bytes
+    }
+
+    /// Builds a 2x3 matrix block with `elements` as its data.
+    fn make_matrix_block(
+        element_type: u32,
+        elements: &[u8],
+    ) -> Vec<u8> {
+        let name = b"CMatrix";
+        let value_offset = CamfBlock::LENGTH + name.len() + 1;
+        let value_header = 12 + 2 * 12;
+
+        let mut value = Vec::new();
+        value.extend_from_slice(&element_type.to_le_bytes());
+        value.extend_from_slice(&2u32.to_le_bytes());
+        value.extend_from_slice(&le_u32(value_offset + value_header));
+        for (index, size) in [2u32, 3].into_iter().enumerate() {
+            value.extend_from_slice(&size.to_le_bytes());
+            value.extend_from_slice(&0u32.to_le_bytes());
+            value.extend_from_slice(&le_u32(index));
+        }
+        value.extend_from_slice(elements);
+        make_block(b"CMbM", name, &value)
+    }
+
+    #[test]
+    fn blocks_walks_consecutive_blocks() {
+        let mut bytes = make_block(b"CMbT", b"Text", b"hello");
+        bytes.extend_from_slice(&make_block(b"CMbP", b"Props", &[1, 2]));
+
+        let blocks: Vec<CamfBlock<'_>> = CamfBlocks::new(&bytes).collect();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].kind(), b'T');
+        assert_eq!(blocks[0].name(), Some(&b"Text"[..]));
+        assert_eq!(blocks[0].value(), Some(&b"hello"[..]));
+        assert_eq!(blocks[1].kind(), b'P');
+        assert_eq!(blocks[1].name(), Some(&b"Props"[..]));
+    }
+
+    #[test]
+    fn blocks_stops_at_malformed_block() {
+        let mut bytes = make_block(b"CMbT", b"Text", b"hello");
+        bytes.extend_from_slice(&[0xFF; CamfBlock::LENGTH]);
+        bytes.extend_from_slice(&make_block(b"CMbT", b"Other", b""));
+
+        let mut blocks = CamfBlocks::new(&bytes);
+        assert!(blocks.next().is_some());
+        assert!(blocks.next().is_none());
+        assert!(blocks.next().is_none());
+    }
+
+    #[test]
+    fn matrix_decodes_i16_elements() {
+        let elements: Vec<u8> = [-1i16, 2, -3, 4, -5, 6]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        let bytes = make_matrix_block(0, &elements);
+        let block = CamfBlocks::new(&bytes).next().expect("block");
+
+        let matrix = block.matrix().expect("matrix");
+        assert_eq!(matrix.element_type(), CamfElementType::I16);
+        assert_eq!(matrix.shape().collect::<Vec<_>>(), [2, 3]);
+        assert_eq!(matrix.element_count(), 6);
+        assert_eq!(
+            matrix.elements().collect::<Vec<_>>(),
+            [-1i16, 2, -3, 4, -5, 6].map(CamfValue::I16)
+        );
+    }
+
+    #[test]
+    fn matrix_decodes_f32_elements() {
+        let elements: Vec<u8> = [0.5f32, 1.0, 1.5, 2.0, 2.5, 3.0]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        let bytes = make_matrix_block(3, &elements);
+        let matrix = CamfBlocks::new(&bytes)
+            .next()
+            .and_then(|block| block.matrix())
+            .expect("matrix");
+
+        assert_eq!(
+            matrix.elements().collect::<Vec<_>>(),
+            [0.5f32, 1.0, 1.5, 2.0, 2.5, 3.0].map(CamfValue::F32)
+        );
+    }
+
+    #[test]
+    fn matrix_rejects_unknown_element_type_and_short_data() {
+        let bytes = make_matrix_block(4, &[0; 24]);
+        let block = CamfBlocks::new(&bytes).next().expect("block");
+        assert!(block.matrix().is_none());
+
+        let bytes = make_matrix_block(1, &[0; 23]);
+        let block = CamfBlocks::new(&bytes).next().expect("block");
+        assert!(block.matrix().is_none());
+    }
+
+    #[test]
+    fn matrix_is_none_for_non_matrix_blocks() {
+        let bytes = make_block(b"CMbT", b"Text", b"hello");
+        let block = CamfBlocks::new(&bytes).next().expect("block");
+        assert!(block.matrix().is_none());
+    }
+}
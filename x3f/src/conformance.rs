@@ -0,0 +1,229 @@
+use alloc::vec::Vec;
+
+use crate::data::expected_section_identifier;
+use crate::{FourCc, Prop, Version, X3F};
+
+/// Kind of deviation from the X3F spec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConformanceCategory {
+    /// A format version the spec does not describe.
+    Version,
+    /// A section identifier that does not match its type.
+    Identifier,
+    /// A data offset that is not a multiple of 4.
+    Alignment,
+    /// A section extending past the end of the file.
+    Bounds,
+    /// A RESERVED field or value in use.
+    Reserved,
+}
+
+/// Where a conformance issue was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConformanceLocation {
+    Header,
+    Directory,
+    /// Directory entry by index, or the section it describes.
+    Entry(usize),
+}
+
+/// Single finding from [`X3F::check_conformance`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConformanceIssue {
+    pub category: ConformanceCategory,
+    pub location: ConformanceLocation,
+    pub message: &'static str,
+}
+
+impl X3F<'_> {
+    /// Runs every validation the crate knows and returns all issues found.
+    ///
+    /// Checks that:
+    /// - the file format version is 2.0 to 2.2,
+    /// - the directory starts with `"SECd"` and has version 2.0,
+    /// - each entry's data offset is a multiple of 4 and its data lies within the file,
+    ///   after the header and extended header,
+    /// - each known section starts with its expected identifier,
+    /// - `PROP` reserved fields are zero and the character format is CHAR16.
+    #[must_use]
+    pub fn check_conformance(&self) -> Vec<ConformanceIssue> {
+        let mut issues = Vec::new();
+        let mut report = |category, location, message| {
+            issues.push(ConformanceIssue {
+                category,
+                location,
+                message,
+            });
+        };
+
+        let version = self.header().version();
+        if !(Version::new(2, 0)..=Version::new(2, 2)).contains(&version) {
+            report(
+                ConformanceCategory::Version,
+                ConformanceLocation::Header,
+                "file format version is not 2.0 to 2.2",
+            );
+        }
+
+        let directory = self.directory();
+        if !directory.verify_identifier() {
+            report(
+                ConformanceCategory::Identifier,
+                ConformanceLocation::Directory,
+                "directory section identifier is not \"SECd\"",
+            );
+        }
+        if directory.section_version() != Version::new(2, 0).raw().to_le_bytes() {
+            report(
+                ConformanceCategory::Version,
+                ConformanceLocation::Directory,
+                "directory section version is not 2.0",
+            );
+        }
+
+        for (index, entry) in directory.entries().enumerate() {
+            let location = ConformanceLocation::Entry(index);
+            if !entry.data_offset_u32().is_multiple_of(4) {
+                report(
+                    ConformanceCategory::Alignment,
+                    location,
+                    "data offset is not a multiple of 4",
+                );
+            }
+
+            if self.starts_in_header(&entry) {
+                report(
+                    ConformanceCategory::Bounds,
+                    location,
+                    "section data starts within the header",
+                );
+            }
+
+            let Some(bytes) = self.section_bytes(&entry) else {
+                report(
+                    ConformanceCategory::Bounds,
+                    location,
+                    "section data extends past the end of the file",
+                );
+                continue;
+            };
+
+            if let Some(expected) = expected_section_identifier(entry.entry_type_fourcc())
+                && !bytes.starts_with(&expected)
+            {
+                report(
+                    ConformanceCategory::Identifier,
+                    location,
+                    "section identifier does not match the entry type",
+                );
+            }
+
+            if entry.entry_type_fourcc() == FourCc::PROP
+                && let Ok(prop) = Prop::from_bytes(bytes)
+                && !prop.is_conformant()
+            {
+                report(
+                    ConformanceCategory::Reserved,
+                    location,
+                    "property list reserved field is not zero or character format is RESERVED",
+                );
+            }
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::test_util::{make_image_header, make_prop, make_x3f, make_x3f_with_version};
+
+    #[test]
+    fn conforming_file_has_no_issues() {
+        let prop = make_prop(&[("ISO", "100")]);
+        let image = make_image_header(2, 18);
+        let bytes = make_x3f_with_version(
+            0x0002_0001,
+            &[(b"PROP", &prop), (b"IMAG", &image), (b"CAMF", b"SECc")],
+        );
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        assert_eq!(x3f.check_conformance(), []);
+    }
+
+    #[test]
+    fn reports_each_issue_with_its_location() {
+        let mut prop = make_prop(&[("ISO", "100")]);
+        prop[16] = 1;
+        let bytes = make_x3f(&[(b"PROP", &prop), (b"IMAG", b"SECpxxxx"), (b"CAMF", &[])]);
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let issues: std::vec::Vec<(ConformanceCategory, ConformanceLocation)> = x3f
+            .check_conformance()
+            .iter()
+            .map(|issue| (issue.category, issue.location))
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                (ConformanceCategory::Version, ConformanceLocation::Header),
+                (ConformanceCategory::Reserved, ConformanceLocation::Entry(0)),
+                (
+                    ConformanceCategory::Identifier,
+                    ConformanceLocation::Entry(1)
+                ),
+                (
+                    ConformanceCategory::Identifier,
+                    ConformanceLocation::Entry(2)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_misaligned_and_out_of_bounds_entries() {
+        let mut bytes =
+            make_x3f_with_version(0x0002_0000, &[(b"XYZW", &[0; 8]), (b"XYZW", &[0; 8])]);
+        let directory = bytes.len() - 4 - 2 * 12 - 12;
+        bytes[directory + 12] += 1;
+        bytes[directory + 24 + 4] = 0xFF;
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let issues: std::vec::Vec<(ConformanceCategory, ConformanceLocation)> = x3f
+            .check_conformance()
+            .iter()
+            .map(|issue| (issue.category, issue.location))
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                (
+                    ConformanceCategory::Alignment,
+                    ConformanceLocation::Entry(0)
+                ),
+                (ConformanceCategory::Bounds, ConformanceLocation::Entry(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_entries_inside_header() {
+        let mut bytes = make_x3f_with_version(0x0002_0000, &[(b"XYZW", &[0; 8])]);
+        let directory = bytes.len() - 4 - 12 - 12;
+        bytes[directory + 12..directory + 16].copy_from_slice(&8u32.to_le_bytes());
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let issues: std::vec::Vec<(ConformanceCategory, ConformanceLocation)> = x3f
+            .check_conformance()
+            .iter()
+            .map(|issue| (issue.category, issue.location))
+            .collect();
+        assert_eq!(
+            issues,
+            [(ConformanceCategory::Bounds, ConformanceLocation::Entry(0))]
+        );
+    }
+}
@@ -1,7 +1,11 @@
 use core::fmt;
 
-use crate::X3FError;
+use crate::byte_order::read_u32_le;
+use crate::camf::{CamfBlock, CamfBlocks};
 use crate::debug_helper::TruncatedBytes;
+use crate::decode::HuffmanTable;
+use crate::utf16::{Utf16Chars, nul_terminated_at};
+use crate::{FourCc, X3FError};
 
 /// # Data Subsection Types
 ///
@@ -19,6 +23,16 @@ pub enum SectionData<'a> {
     Camf(Camf<'a>),
 }
 
+/// Returns the identifier a section of type `tag` must start with, for known types.
+pub(crate) fn expected_section_identifier(tag: FourCc) -> Option<[u8; 4]> {
+    match tag {
+        FourCc::PROP => Some(Prop::SECTION_IDENTIFIER),
+        FourCc::IMAG | FourCc::IMA2 => Some(Image::SECTION_IDENTIFIER),
+        FourCc::CAMF => Some(Camf::SECTION_IDENTIFIER),
+        _ => None,
+    }
+}
+
 /// # Structure
 ///
 /// | Offset | Length | Item | Notes |
@@ -29,6 +43,9 @@ pub enum SectionData<'a> {
 /// | 12 | 4 | Character format for all entries in this table. | 0 = CHAR16 Unicode. |
 /// | 16 | 4 | RESERVED |  |
 /// | 20 | 4 | Total length of name/value data in characters. |
+/// | 24 | 8 × N | Entry table | Pairs of name/value offsets, in characters from the start of the character data. |
+/// | 24 + 8 × N | 2 × length | Character data | NUL-terminated CHAR16 strings. |
+#[derive(PartialEq, Eq, Hash)]
 pub struct Prop<'a> {
     bytes: &'a [u8],
 }
@@ -46,6 +63,7 @@ impl fmt::Debug for Prop<'_> {
 
 impl<'a> Prop<'a> {
     pub const LENGTH: usize = 24;
+    pub const SECTION_IDENTIFIER: [u8; 4] = *b"SECp";
 
     /// Creates a new `Prop` from the given byte slice.
     ///
@@ -60,6 +78,25 @@ impl<'a> Prop<'a> {
         Ok(Self { bytes })
     }
 
+    /// Like [`Self::from_bytes`], but also checks that `bytes` starts with
+    /// [`Self::SECTION_IDENTIFIER`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`X3FError::TooShort`] if `bytes.len() < Self::LENGTH`, or
+    /// [`X3FError::InvalidSectionIdentifier`] if the identifier does not match.
+    pub fn from_section_bytes(bytes: &'a [u8]) -> Result<Self, X3FError> {
+        let section = Self::from_bytes(bytes)?;
+        if !section.verify_identifier() {
+            return Err(X3FError::InvalidSectionIdentifier {
+                expected: Self::SECTION_IDENTIFIER,
+                found: bytes[0..4].try_into().unwrap_or_default(),
+            });
+        }
+
+        Ok(section)
+    }
+
     #[must_use]
     pub fn as_bytes(&self) -> &'a [u8] {
         self.bytes
@@ -70,6 +107,12 @@ impl<'a> Prop<'a> {
         &self.bytes[0..4]
     }
 
+    /// Returns `true` if the section starts with [`Self::SECTION_IDENTIFIER`].
+    #[must_use]
+    pub fn verify_identifier(&self) -> bool {
+        self.section_identifier() == Self::SECTION_IDENTIFIER
+    }
+
     #[must_use]
     pub fn property_list_format_version(&self) -> &'a [u8] {
         &self.bytes[4..8]
@@ -94,19 +137,201 @@ impl<'a> Prop<'a> {
     pub fn total_length_of_name_value_data(&self) -> &'a [u8] {
         &self.bytes[20..24]
     }
+
+    /// Returns the declared length of the character data, in characters.
+    #[must_use]
+    pub fn total_length_of_name_value_data_u32(&self) -> u32 {
+        read_u32_le(self.total_length_of_name_value_data(), 0).unwrap_or(0)
+    }
+
+    /// Returns `true` if the bytes after the entry table hold exactly the declared
+    /// number of characters.
+    ///
+    /// A mismatch indicates a truncated section or a miscounted length.
+    #[must_use]
+    pub fn char_data_matches_declared(&self) -> bool {
+        self.declared_character_data_len() == Some(self.available_character_data().len())
+    }
+
+    #[must_use]
+    pub fn character_format_enum(&self) -> CharacterFormat {
+        CharacterFormat::from_u32(read_u32_le(self.character_format(), 0).unwrap_or(0))
+    }
+
+    /// Returns `true` if the reserved field is zero and the character format is recognized.
+    ///
+    /// `X3F::check_conformance` reports sections failing this check.
+    #[must_use]
+    pub fn is_conformant(&self) -> bool {
+        self.reserved() == [0u8; 4] && self.character_format_enum() == CharacterFormat::Char16
+    }
+
+    /// Returns the value of the first property named `name`.
+    #[must_use]
+    pub fn get(
+        &self,
+        name: &str,
+    ) -> Option<Utf16Chars<'a>> {
+        self.entries()
+            .find(|(entry_name, _)| entry_name.clone().eq(name.chars()))
+            .map(|(_, value)| value)
+    }
+
+    /// Iterates over the decoded name/value pairs in entry table order.
+    ///
+    /// Entries whose name or value offset falls outside the character data are skipped.
+    pub fn entries(&self) -> impl Iterator<Item = (Utf16Chars<'a>, Utf16Chars<'a>)> + use<'a> {
+        self.raw_entries()
+            .map(|(name, value)| (Utf16Chars::new(name), Utf16Chars::new(value)))
+    }
+
+    /// Decodes the `index`-th pair yielded by [`Self::entries`] as UTF-8 into `name_buf`
+    /// and `value_buf`, without allocating.
+    ///
+    /// Returns the full UTF-8 lengths of the name and value. A string longer than its
+    /// buffer is truncated at a character boundary, so a returned length greater than
+    /// the buffer length signals truncation and gives the size needed. Returns `None` if
+    /// there is no such entry.
+    pub fn decode_entry_into(
+        &self,
+        index: usize,
+        name_buf: &mut [u8],
+        value_buf: &mut [u8],
+    ) -> Option<(usize, usize)> {
+        let (name, value) = self.entries().nth(index)?;
+        Some((
+            encode_utf8_into(name, name_buf),
+            encode_utf8_into(value, value_buf),
+        ))
+    }
+
+    /// Iterates over the undecoded UTF-16LE name/value pairs in entry table order,
+    /// excluding their NUL terminators.
+    ///
+    /// Entries whose name or value offset falls outside the character data are skipped.
+    pub fn raw_entries(&self) -> impl Iterator<Item = (&'a [u8], &'a [u8])> + use<'a> {
+        let data = self.character_data();
+        self.entry_offsets().filter_map(move |(name, value)| {
+            Some((
+                nul_terminated_at(data, name)?,
+                nul_terminated_at(data, value)?,
+            ))
+        })
+    }
+
+    /// Iterates over the decoded property names without touching their values.
+    ///
+    /// Entries whose name offset falls outside the character data are skipped.
+    pub fn names(&self) -> impl Iterator<Item = Utf16Chars<'a>> + use<'a> {
+        let data = self.character_data();
+        self.entry_offsets()
+            .filter_map(move |(name, _)| nul_terminated_at(data, name).map(Utf16Chars::new))
+    }
+
+    /// Returns the declared number of property entries, which may exceed what the bytes hold.
+    #[must_use]
+    pub fn entry_count(&self) -> u32 {
+        read_u32_le(self.number_of_property_entries(), 0).unwrap_or(0)
+    }
+
+    /// Returns the declared entry count, or `None` if the entry table would not fit in the
+    /// section. Such a section is malformed: clamping the count would read character data
+    /// as entries.
+    fn checked_entry_count(&self) -> Option<usize> {
+        let declared = usize::try_from(self.entry_count()).ok()?;
+        (declared <= (self.bytes.len() - Self::LENGTH) / 8).then_some(declared)
+    }
+
+    fn entry_offsets(&self) -> impl Iterator<Item = (u32, u32)> + use<'a> {
+        self.bytes[Self::LENGTH..]
+            .chunks_exact(8)
+            .take(self.checked_entry_count().unwrap_or(0))
+            .filter_map(|entry| Some((read_u32_le(entry, 0)?, read_u32_le(entry, 4)?)))
+    }
+
+    /// Returns the character data, limited to the declared length when it is shorter
+    /// than what the section holds.
+    fn character_data(&self) -> &'a [u8] {
+        let available = self.available_character_data();
+        self.declared_character_data_len()
+            .and_then(|len| available.get(..len))
+            .unwrap_or(available)
+    }
+
+    fn available_character_data(&self) -> &'a [u8] {
+        self.checked_entry_count()
+            .map_or(&[], |count| &self.bytes[Self::LENGTH + count * 8..])
+    }
+
+    fn declared_character_data_len(&self) -> Option<usize> {
+        usize::try_from(self.total_length_of_name_value_data_u32())
+            .ok()?
+            .checked_mul(2)
+    }
+}
+
+/// Writes `chars` as UTF-8 into `buf`, stopping before the first character that does not
+/// fit, and returns the length of the complete encoding.
+fn encode_utf8_into(
+    chars: Utf16Chars<'_>,
+    buf: &mut [u8],
+) -> usize {
+    let mut needed = 0;
+    let mut fits = true;
+    for ch in chars {
+        let end = needed + ch.len_utf8();
+        fits = fits && end <= buf.len();
+        if fits {
+            ch.encode_utf8(&mut buf[needed..end]);
+        }
+        needed = end;
+    }
+    needed
+}
+
+/// Character format of a property list, as stored at offset 12.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CharacterFormat {
+    /// `0`: CHAR16 Unicode (UTF-16LE).
+    Char16,
+    /// Any other value; RESERVED by the spec.
+    Reserved(u32),
+}
+
+impl CharacterFormat {
+    #[must_use]
+    pub const fn from_u32(value: u32) -> Self {
+        match value {
+            0 => Self::Char16,
+            other => Self::Reserved(other),
+        }
+    }
+
+    #[must_use]
+    pub const fn as_u32(self) -> u32 {
+        match self {
+            Self::Char16 => 0,
+            Self::Reserved(value) => value,
+        }
+    }
 }
 
 /// # Structure
 ///
 /// | Offset | Length | Item | Notes |
 /// | --- | --- | --- | --- |
-/// | 0 | 4 | Section identifier | Should be `"SECp"` |
+/// | 0 | 4 | Section identifier | Should be `"SECi"` |
 /// | 4 | 4 | Image format version | Should be 2.0 for now. |
 /// | 8 | 4 | Type of image data | 2 = processed for preview (others RESERVED) |
 /// | 12 | 4 | Data format | 3 = uncompressed 24-bit 8/8/8 RGB, 11 = Huffman-encoded DPCM 8/8/8 RGB, 18 = JPEG-compressed 8/8/8 RGB (others RESERVED) |
 /// | 16 | 4 | Image columns | Image width / row size in pixels |
 /// | 20 | 4 | Image rows | Image height in pixels |
 /// | 24 | 4 | Row size in bytes | Will always be a multiple of 4 (32-bit aligned). A value of zero here means that rows are variable-length (as in Huffman data). |
+/// | 28 | | Image data | |
+///
+/// `Image` borrows the whole section, so both the header fields and the image data are
+/// available from a single value.
+#[derive(PartialEq, Eq, Hash)]
 pub struct Image<'a> {
     bytes: &'a [u8],
 }
@@ -124,6 +349,7 @@ impl fmt::Debug for Image<'_> {
 
 impl<'a> Image<'a> {
     pub const LENGTH: usize = 28;
+    pub const SECTION_IDENTIFIER: [u8; 4] = *b"SECi";
 
     /// Creates a new `Image` from the given byte slice.
     ///
@@ -138,6 +364,25 @@ impl<'a> Image<'a> {
         Ok(Self { bytes })
     }
 
+    /// Like [`Self::from_bytes`], but also checks that `bytes` starts with
+    /// [`Self::SECTION_IDENTIFIER`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`X3FError::TooShort`] if `bytes.len() < Self::LENGTH`, or
+    /// [`X3FError::InvalidSectionIdentifier`] if the identifier does not match.
+    pub fn from_section_bytes(bytes: &'a [u8]) -> Result<Self, X3FError> {
+        let section = Self::from_bytes(bytes)?;
+        if !section.verify_identifier() {
+            return Err(X3FError::InvalidSectionIdentifier {
+                expected: Self::SECTION_IDENTIFIER,
+                found: bytes[0..4].try_into().unwrap_or_default(),
+            });
+        }
+
+        Ok(section)
+    }
+
     #[must_use]
     pub fn as_bytes(&self) -> &'a [u8] {
         self.bytes
@@ -148,6 +393,12 @@ impl<'a> Image<'a> {
         &self.bytes[0..4]
     }
 
+    /// Returns `true` if the section starts with [`Self::SECTION_IDENTIFIER`].
+    #[must_use]
+    pub fn verify_identifier(&self) -> bool {
+        self.section_identifier() == Self::SECTION_IDENTIFIER
+    }
+
     #[must_use]
     pub fn image_format_version(&self) -> &'a [u8] {
         &self.bytes[4..8]
@@ -177,12 +428,321 @@ impl<'a> Image<'a> {
     pub fn row_size_in_bytes(&self) -> &'a [u8] {
         &self.bytes[24..28]
     }
+
+    #[must_use]
+    pub fn image_columns_u32(&self) -> u32 {
+        read_u32_le(self.image_columns(), 0).unwrap_or(0)
+    }
+
+    #[must_use]
+    pub fn image_rows_u32(&self) -> u32 {
+        read_u32_le(self.image_rows(), 0).unwrap_or(0)
+    }
+
+    /// Returns `image_columns * image_rows`, the logical number of pixels.
+    ///
+    /// This ignores the channel count and compression, so it is not a byte count.
+    #[must_use]
+    pub fn pixel_count(&self) -> u64 {
+        u64::from(self.image_columns_u32()) * u64::from(self.image_rows_u32())
+    }
+
+    /// Returns `columns * rows * 3`, the length of the image as tightly packed RGB bytes,
+    /// or `None` if it overflows a `u64`.
+    #[must_use]
+    pub fn checked_rgb_len(&self) -> Option<u64> {
+        checked_pixel_bytes(self.image_columns_u32(), self.image_rows_u32(), 3)
+    }
+
+    /// Estimates how many times smaller the compressed image is than its RGB24 equivalent,
+    /// given the length of its section as reported by the directory.
+    ///
+    /// Returns `None` for uncompressed or RESERVED formats, zero dimensions, and an empty
+    /// section.
+    #[must_use]
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "an estimate does not need exact lengths"
+    )]
+    pub fn compression_ratio(
+        &self,
+        section_len: usize,
+    ) -> Option<f32> {
+        if !matches!(
+            self.data_format_enum(),
+            DataFormat::HuffmanDpcm888 | DataFormat::Jpeg
+        ) || section_len == 0
+        {
+            return None;
+        }
+
+        let uncompressed = self.checked_rgb_len().filter(|&len| len != 0)?;
+        Some(uncompressed as f32 / section_len as f32)
+    }
+
+    #[must_use]
+    pub fn image_type(&self) -> ImageType {
+        ImageType::from_u32(read_u32_le(self.type_of_image_data(), 0).unwrap_or(0))
+    }
+
+    #[must_use]
+    pub fn data_format_enum(&self) -> DataFormat {
+        DataFormat::from_u32(read_u32_le(self.data_format(), 0).unwrap_or(0))
+    }
+
+    /// Returns `true` if this crate can decode the image data.
+    ///
+    /// Only uncompressed RGB24 is supported so far; callers should fall back to
+    /// [`Self::pixel_data`] for other formats.
+    #[must_use]
+    pub fn is_decodable(&self) -> bool {
+        self.data_format_enum().is_decodable()
+    }
+
+    /// Returns the number of bytes per row of image data.
+    ///
+    /// Uses the declared row size when non-zero. Otherwise, for uncompressed RGB24 data,
+    /// computes the minimum stride of `columns * 3` rounded up to a multiple of 4.
+    /// Returns `None` for Huffman and JPEG data, whose rows are variable-length, and
+    /// for RESERVED formats whose declared row size is zero.
+    #[must_use]
+    pub fn row_stride(&self) -> Option<u32> {
+        let format = self.data_format_enum();
+        if matches!(format, DataFormat::HuffmanDpcm888 | DataFormat::Jpeg) {
+            return None;
+        }
+
+        let declared = read_u32_le(self.row_size_in_bytes(), 0)?;
+        if declared != 0 {
+            return Some(declared);
+        }
+        if format != DataFormat::UncompressedRgb24 {
+            return None;
+        }
+
+        read_u32_le(self.image_columns(), 0)?
+            .checked_mul(3)?
+            .checked_next_multiple_of(4)
+    }
+
+    /// Iterates over the rows of uncompressed RGB24 image data, each `columns * 3` bytes
+    /// long with any row padding removed.
+    ///
+    /// Returns `None` if the data is not uncompressed RGB24 or holds fewer rows than declared.
+    #[must_use]
+    pub fn rgb24_rows(&self) -> Option<impl Iterator<Item = &'a [u8]> + use<'a>> {
+        self.rgb24_rows_in(self.pixel_data())
+    }
+
+    /// Writes the uncompressed RGB24 image data within `section_bytes`, as returned by
+    /// [`crate::X3F::section_bytes`], into `out` as tightly packed rows without padding.
+    ///
+    /// Returns the number of bytes written, `columns * rows * 3`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`X3FError::UnsupportedDataFormat`] if the data is not uncompressed RGB24,
+    /// [`X3FError::BufferTooSmall`] if `out` cannot hold the image, and
+    /// [`X3FError::TooShort`] if `section_bytes` holds fewer rows than declared.
+    pub fn decode_into(
+        &self,
+        section_bytes: &[u8],
+        out: &mut [u8],
+    ) -> Result<usize, X3FError> {
+        if self.data_format_enum() != DataFormat::UncompressedRgb24 {
+            return Err(X3FError::UnsupportedDataFormat);
+        }
+
+        let required = self
+            .checked_rgb_len()
+            .and_then(|len| usize::try_from(len).ok())
+            .ok_or(X3FError::OutOfBounds)?;
+        let out = out
+            .get_mut(..required)
+            .ok_or(X3FError::BufferTooSmall { required })?;
+
+        let rows = section_bytes
+            .get(Self::LENGTH..)
+            .and_then(|data| self.rgb24_rows_in(data))
+            .ok_or(X3FError::TooShort)?;
+        let mut written = 0;
+        for row in rows {
+            out[written..written + row.len()].copy_from_slice(row);
+            written += row.len();
+        }
+        Ok(written)
+    }
+
+    fn rgb24_rows_in<'b>(
+        &self,
+        data: &'b [u8],
+    ) -> Option<impl Iterator<Item = &'b [u8]> + use<'b>> {
+        if self.data_format_enum() != DataFormat::UncompressedRgb24 {
+            return None;
+        }
+
+        let row_len = usize::try_from(self.image_columns_u32())
+            .ok()?
+            .checked_mul(3)?;
+        let stride = usize::try_from(self.row_stride()?).ok()?;
+        let rows = usize::try_from(self.image_rows_u32()).ok()?;
+        if row_len > stride {
+            return None;
+        }
+        let required = match rows.checked_sub(1) {
+            Some(last) => last.checked_mul(stride)?.checked_add(row_len)?,
+            None => 0,
+        };
+
+        let data = data.get(..required)?;
+        Some((0..rows).map(move |row| &data[row * stride..row * stride + row_len]))
+    }
+
+    /// Returns the image data following the 28-byte header.
+    #[must_use]
+    pub fn pixel_data(&self) -> &'a [u8] {
+        &self.bytes[Self::LENGTH..]
+    }
+
+    /// Parses the Huffman code table at the start of [`Self::pixel_data`].
+    ///
+    /// Returns `None` if the data format is not `HuffmanDpcm888` or the table is malformed.
+    #[must_use]
+    pub fn huffman_table(&self) -> Option<HuffmanTable> {
+        if self.data_format_enum() != DataFormat::HuffmanDpcm888 {
+            return None;
+        }
+        HuffmanTable::from_bytes(self.pixel_data())
+    }
+
+    /// Returns `true` if the image data is processed for preview.
+    #[must_use]
+    pub fn is_preview(&self) -> bool {
+        self.image_type() == ImageType::ProcessedForPreview
+    }
+
+    /// Returns `true` if the image data is JPEG-compressed.
+    #[must_use]
+    pub fn is_jpeg(&self) -> bool {
+        self.data_format_enum() == DataFormat::Jpeg
+    }
+
+    /// Returns `true` if the image data is uncompressed 24-bit RGB.
+    #[must_use]
+    pub fn is_uncompressed_rgb(&self) -> bool {
+        self.data_format_enum() == DataFormat::UncompressedRgb24
+    }
+}
+
+/// Type of image data, as stored at offset 8 of an image section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageType {
+    /// `2`: processed for preview.
+    ProcessedForPreview,
+    /// Any other value; RESERVED by the spec.
+    Reserved(u32),
+}
+
+impl ImageType {
+    #[must_use]
+    pub const fn from_u32(value: u32) -> Self {
+        match value {
+            2 => Self::ProcessedForPreview,
+            other => Self::Reserved(other),
+        }
+    }
+
+    #[must_use]
+    pub const fn as_u32(self) -> u32 {
+        match self {
+            Self::ProcessedForPreview => 2,
+            Self::Reserved(value) => value,
+        }
+    }
+}
+
+/// Returns `columns * rows * channels`, or `None` if it overflows a `u64`.
+fn checked_pixel_bytes(
+    columns: u32,
+    rows: u32,
+    channels: u32,
+) -> Option<u64> {
+    u64::from(columns)
+        .checked_mul(u64::from(rows))?
+        .checked_mul(u64::from(channels))
+}
+
+/// Data format of an image section, as stored at offset 12.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataFormat {
+    /// `3`: uncompressed 24-bit 8/8/8 RGB.
+    UncompressedRgb24,
+    /// `11`: Huffman-encoded DPCM 8/8/8 RGB.
+    HuffmanDpcm888,
+    /// `18`: JPEG-compressed 8/8/8 RGB.
+    Jpeg,
+    /// Any other value; RESERVED by the spec.
+    Reserved(u32),
+}
+
+impl DataFormat {
+    /// Returns every format the spec defines, in ascending order of value.
+    #[must_use]
+    pub const fn all_known() -> &'static [Self] {
+        &[Self::UncompressedRgb24, Self::HuffmanDpcm888, Self::Jpeg]
+    }
+
+    /// Returns `true` if this crate can decode image data in this format.
+    #[must_use]
+    pub const fn is_decodable(self) -> bool {
+        match self {
+            Self::UncompressedRgb24 => true,
+            Self::HuffmanDpcm888 | Self::Jpeg | Self::Reserved(_) => false,
+        }
+    }
+
+    /// Returns a short human-readable name for the format, such as `"JPEG-compressed RGB"`.
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::UncompressedRgb24 => "uncompressed 24-bit RGB",
+            Self::HuffmanDpcm888 => "Huffman-encoded DPCM RGB",
+            Self::Jpeg => "JPEG-compressed RGB",
+            Self::Reserved(_) => "reserved",
+        }
+    }
+
+    #[must_use]
+    pub const fn from_u32(value: u32) -> Self {
+        match value {
+            3 => Self::UncompressedRgb24,
+            11 => Self::HuffmanDpcm888,
+            18 => Self::Jpeg,
+            other => Self::Reserved(other),
+        }
+    }
+
+    #[must_use]
+    pub const fn as_u32(self) -> u32 {
+        match self {
+            Self::UncompressedRgb24 => 3,
+            Self::HuffmanDpcm888 => 11,
+            Self::Jpeg => 18,
+            Self::Reserved(value) => value,
+        }
+    }
 }
 
 /// Raw CAMF section data.
 ///
 /// The CAMF structure is not documented in the public X3F spec, so we only
-/// expose the raw bytes for now.
+/// expose the raw bytes for now. `Camf` borrows the whole section.
+///
+/// Community reverse engineering describes a 28-byte header (identifier, version,
+/// CAMF type and four type-specific words) followed by a sequence of `"CMb?"` blocks.
+/// Most cameras encrypt or compress the blocks; [`Camf::blocks`] only yields blocks
+/// stored in the clear.
+#[derive(PartialEq, Eq, Hash)]
 pub struct Camf<'a> {
     bytes: &'a [u8],
 }
@@ -200,6 +760,8 @@ impl fmt::Debug for Camf<'_> {
 
 impl<'a> Camf<'a> {
     pub const LENGTH: usize = 4;
+    pub const HEADER_LENGTH: usize = 28;
+    pub const SECTION_IDENTIFIER: [u8; 4] = *b"SECc";
 
     /// Creates a new `Camf` from the given byte slice.
     ///
@@ -214,6 +776,25 @@ impl<'a> Camf<'a> {
         Ok(Self { bytes })
     }
 
+    /// Like [`Self::from_bytes`], but also checks that `bytes` starts with
+    /// [`Self::SECTION_IDENTIFIER`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`X3FError::TooShort`] if `bytes.len() < Self::LENGTH`, or
+    /// [`X3FError::InvalidSectionIdentifier`] if the identifier does not match.
+    pub fn from_section_bytes(bytes: &'a [u8]) -> Result<Self, X3FError> {
+        let section = Self::from_bytes(bytes)?;
+        if !section.verify_identifier() {
+            return Err(X3FError::InvalidSectionIdentifier {
+                expected: Self::SECTION_IDENTIFIER,
+                found: bytes[0..4].try_into().unwrap_or_default(),
+            });
+        }
+
+        Ok(section)
+    }
+
     #[must_use]
     pub fn as_bytes(&self) -> &'a [u8] {
         self.bytes
@@ -223,6 +804,38 @@ impl<'a> Camf<'a> {
     pub fn section_identifier(&self) -> &'a [u8] {
         &self.bytes[0..4]
     }
+
+    /// Returns `true` if the section starts with [`Self::SECTION_IDENTIFIER`].
+    #[must_use]
+    pub fn verify_identifier(&self) -> bool {
+        self.section_identifier() == Self::SECTION_IDENTIFIER
+    }
+
+    /// Returns everything after the section identifier.
+    #[must_use]
+    pub fn payload(&self) -> &'a [u8] {
+        &self.bytes[Self::LENGTH..]
+    }
+
+    /// Iterates over the blocks following the 28-byte CAMF header.
+    ///
+    /// Yields nothing when the header is truncated or the blocks are encrypted or
+    /// compressed. Use [`CamfBlocks::new`] on decoded data in that case.
+    #[must_use]
+    pub fn blocks(&self) -> CamfBlocks<'a> {
+        CamfBlocks::new(self.bytes.get(Self::HEADER_LENGTH..).unwrap_or(&[]))
+    }
+
+    /// Iterates over the same blocks as [`Self::blocks`], each paired with its offset from
+    /// the start of the CAMF section, for cross-referencing with hex dumps.
+    pub fn blocks_with_offset(&self) -> impl Iterator<Item = (usize, CamfBlock<'a>)> + use<'a> {
+        let mut offset = Self::HEADER_LENGTH;
+        self.blocks().map(move |block| {
+            let block_offset = offset;
+            offset += block.as_bytes().len();
+            (block_offset, block)
+        })
+    }
 }
 
 #[cfg(test)]
@@ -230,6 +843,123 @@ mod tests {
     extern crate std;
 
     use super::*;
+    use crate::test_util::{make_image_header, make_prop};
+    use std::string::String;
+    use std::vec::Vec;
+
+    #[test]
+    fn prop_entries_decodes_pairs() {
+        let bytes = make_prop(&[("CAMMANUF", "SIGMA"), ("CAMMODEL", "SIGMA DP2 Merrill")]);
+        let prop = Prop::from_bytes(&bytes).expect("valid prop");
+
+        let entries: Vec<(String, String)> = prop
+            .entries()
+            .map(|(name, value)| (name.collect(), value.collect()))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                (String::from("CAMMANUF"), String::from("SIGMA")),
+                (String::from("CAMMODEL"), String::from("SIGMA DP2 Merrill")),
+            ]
+        );
+    }
+
+    #[test]
+    fn prop_raw_entries_yields_utf16_bytes() {
+        let mut bytes = make_prop(&[("ISO", "100"), ("FLENGTH", "30.0")]);
+        // Point the second entry's value past the character data.
+        bytes[Prop::LENGTH + 12..Prop::LENGTH + 16].copy_from_slice(&1000u32.to_le_bytes());
+        let prop = Prop::from_bytes(&bytes).expect("valid prop");
+
+        let entries: Vec<(&[u8], &[u8])> = prop.raw_entries().collect();
+        assert_eq!(entries, [(&b"I\0S\0O\0"[..], &b"1\x000\x000\0"[..])]);
+    }
+
+    #[test]
+    fn prop_char_data_matches_declared_detects_mismatch() {
+        let bytes = make_prop(&[("ISO", "100")]);
+        let prop = Prop::from_bytes(&bytes).expect("valid prop");
+        assert_eq!(prop.total_length_of_name_value_data_u32(), 8);
+        assert!(prop.char_data_matches_declared());
+
+        let truncated = &bytes[..bytes.len() - 2];
+        let prop = Prop::from_bytes(truncated).expect("valid prop");
+        assert!(!prop.char_data_matches_declared());
+        let values: Vec<String> = prop.entries().map(|(_, value)| value.collect()).collect();
+        assert_eq!(values, ["100"]);
+    }
+
+    #[test]
+    fn prop_entries_stop_at_declared_length() {
+        let mut bytes = make_prop(&[("ISO", "100")]);
+        bytes[20..24].copy_from_slice(&3u32.to_le_bytes());
+        bytes.extend_from_slice(&[0x41, 0x00, 0x00, 0x00]);
+        let prop = Prop::from_bytes(&bytes).expect("valid prop");
+
+        assert!(!prop.char_data_matches_declared());
+        let names: Vec<String> = prop.names().map(Iterator::collect).collect();
+        assert_eq!(names, ["ISO"]);
+        assert_eq!(prop.entries().count(), 0);
+    }
+
+    #[test]
+    fn prop_entry_count_overrunning_section_yields_nothing() {
+        let mut bytes = make_prop(&[("ISO", "100"), ("FLENGTH", "30.0")]);
+        bytes[8..12].copy_from_slice(&1u32.to_le_bytes());
+        bytes.drain(Prop::LENGTH + 8..Prop::LENGTH + 16);
+        let prop = Prop::from_bytes(&bytes).expect("valid prop");
+        assert_eq!(prop.entry_count(), 1);
+        let names: Vec<String> = prop.names().map(Iterator::collect).collect();
+        assert_eq!(names, ["ISO"]);
+
+        bytes[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+        let prop = Prop::from_bytes(&bytes).expect("valid prop");
+        assert_eq!(prop.entry_count(), u32::MAX);
+        assert_eq!(prop.entries().count(), 0);
+        assert_eq!(prop.names().count(), 0);
+        assert!(!prop.char_data_matches_declared());
+    }
+
+    #[test]
+    fn prop_decode_entry_into_writes_utf8() {
+        let bytes = make_prop(&[("ISO", "100"), ("CAMMODEL", "dp2 \u{00C9}")]);
+        let prop = Prop::from_bytes(&bytes).expect("valid prop");
+
+        let mut name = [0u8; 16];
+        let mut value = [0u8; 16];
+        assert_eq!(
+            prop.decode_entry_into(1, &mut name, &mut value),
+            Some((8, 6))
+        );
+        assert_eq!(&name[..8], b"CAMMODEL");
+        assert_eq!(&value[..6], "dp2 \u{00C9}".as_bytes());
+        assert_eq!(prop.decode_entry_into(2, &mut name, &mut value), None);
+    }
+
+    #[test]
+    fn prop_decode_entry_into_truncates_at_char_boundary() {
+        let bytes = make_prop(&[("CAMMODEL", "dp2 \u{00C9}")]);
+        let prop = Prop::from_bytes(&bytes).expect("valid prop");
+
+        let mut name = [0u8; 3];
+        let mut value = [0xAAu8; 5];
+        assert_eq!(
+            prop.decode_entry_into(0, &mut name, &mut value),
+            Some((8, 6))
+        );
+        assert_eq!(&name, b"CAM");
+        assert_eq!(value, [b'd', b'p', b'2', b' ', 0xAA]);
+    }
+
+    #[test]
+    fn prop_names_yields_only_names() {
+        let bytes = make_prop(&[("ISO", "100"), ("FLENGTH", "30.0")]);
+        let prop = Prop::from_bytes(&bytes).expect("valid prop");
+
+        let names: Vec<String> = prop.names().map(Iterator::collect).collect();
+        assert_eq!(names, ["ISO", "FLENGTH"]);
+    }
 
     #[test]
     fn prop_from_bytes_rejects_short_input() {
@@ -251,6 +981,347 @@ mod tests {
         }
     }
 
+    #[test]
+    fn prop_get_finds_value_by_name() {
+        let bytes = make_prop(&[("CAMMANUF", "SIGMA"), ("ISO", "200")]);
+        let prop = Prop::from_bytes(&bytes).expect("valid prop");
+
+        assert_eq!(
+            prop.get("ISO").map(Iterator::collect::<String>).as_deref(),
+            Some("200")
+        );
+        assert!(prop.get("IS").is_none());
+    }
+
+    #[test]
+    fn prop_is_conformant_checks_reserved_and_character_format() {
+        let mut bytes = make_prop(&[("ISO", "100")]);
+        assert!(
+            Prop::from_bytes(&bytes)
+                .expect("valid prop")
+                .is_conformant()
+        );
+
+        bytes[16] = 1;
+        assert!(
+            !Prop::from_bytes(&bytes)
+                .expect("valid prop")
+                .is_conformant()
+        );
+
+        bytes[16] = 0;
+        bytes[12] = 1;
+        let prop = Prop::from_bytes(&bytes).expect("valid prop");
+        assert_eq!(prop.character_format_enum(), CharacterFormat::Reserved(1));
+        assert!(!prop.is_conformant());
+    }
+
+    #[test]
+    fn image_predicates_follow_type_and_format() {
+        let bytes = make_image_header(2, 18);
+        let image = Image::from_bytes(&bytes).expect("valid image");
+        assert!(image.is_preview());
+        assert!(image.is_jpeg());
+        assert!(!image.is_uncompressed_rgb());
+
+        let bytes = make_image_header(1, 3);
+        let image = Image::from_bytes(&bytes).expect("valid image");
+        assert!(!image.is_preview());
+        assert!(!image.is_jpeg());
+        assert!(image.is_uncompressed_rgb());
+    }
+
+    #[test]
+    fn image_pixel_data_skips_header() {
+        let mut bytes = make_image_header(2, 3).to_vec();
+        bytes.extend_from_slice(&[1, 2, 3, 4]);
+        let image = Image::from_bytes(&bytes).expect("valid image");
+
+        assert_eq!(image.pixel_data(), &[1, 2, 3, 4]);
+        assert!(Image::from_bytes(&bytes[..Image::LENGTH - 1]).is_err());
+    }
+
+    #[test]
+    fn image_is_decodable_only_for_supported_formats() {
+        for (format, decodable) in [(3, true), (11, false), (18, false), (0, false)] {
+            let bytes = make_image_header(2, format);
+            let image = Image::from_bytes(&bytes).expect("valid image");
+            assert_eq!(image.is_decodable(), decodable, "format {format}");
+        }
+    }
+
+    #[test]
+    fn image_pixel_count_does_not_overflow_u32() {
+        let mut bytes = make_image_header(2, 3);
+        bytes[16..20].copy_from_slice(&u32::MAX.to_le_bytes());
+        bytes[20..24].copy_from_slice(&3u32.to_le_bytes());
+        let image = Image::from_bytes(&bytes).expect("valid image");
+        assert_eq!(image.pixel_count(), u64::from(u32::MAX) * 3);
+    }
+
+    #[test]
+    fn checked_pixel_bytes_detects_overflow() {
+        let max = u64::from(u32::MAX);
+        assert_eq!(checked_pixel_bytes(u32::MAX, u32::MAX, 1), Some(max * max));
+        assert_eq!(checked_pixel_bytes(u32::MAX, u32::MAX, 3), None);
+        assert_eq!(checked_pixel_bytes(u32::MAX, 1, u32::MAX), Some(max * max));
+        assert_eq!(checked_pixel_bytes(0, u32::MAX, u32::MAX), Some(0));
+
+        let mut bytes = make_image_header(2, 3);
+        bytes[16..20].copy_from_slice(&u32::MAX.to_le_bytes());
+        bytes[20..24].copy_from_slice(&2u32.to_le_bytes());
+        let image = Image::from_bytes(&bytes).expect("valid image");
+        assert_eq!(image.checked_rgb_len(), Some(max * 6));
+
+        bytes[20..24].copy_from_slice(&u32::MAX.to_le_bytes());
+        let image = Image::from_bytes(&bytes).expect("valid image");
+        assert_eq!(image.checked_rgb_len(), None);
+    }
+
+    #[test]
+    fn image_compression_ratio_covers_compressed_formats() {
+        let mut bytes = make_image_header(2, 18);
+        bytes[16..20].copy_from_slice(&100u32.to_le_bytes());
+        bytes[20..24].copy_from_slice(&50u32.to_le_bytes());
+        let ratio = |bytes: &[u8], len| {
+            Image::from_bytes(bytes)
+                .expect("valid image")
+                .compression_ratio(len)
+        };
+
+        assert_eq!(ratio(&bytes, 1500), Some(10.0));
+        assert_eq!(ratio(&bytes, 0), None);
+        bytes[12..16].copy_from_slice(&11u32.to_le_bytes());
+        assert_eq!(ratio(&bytes, 3000), Some(5.0));
+        bytes[12..16].copy_from_slice(&3u32.to_le_bytes());
+        assert_eq!(ratio(&bytes, 1500), None);
+        bytes[12..16].copy_from_slice(&18u32.to_le_bytes());
+        bytes[20..24].copy_from_slice(&0u32.to_le_bytes());
+        assert_eq!(ratio(&bytes, 1500), None);
+    }
+
+    #[test]
+    fn image_rgb24_rows_strips_row_padding() {
+        let mut bytes = make_image_header(2, 3).to_vec();
+        bytes[16..20].copy_from_slice(&1u32.to_le_bytes());
+        bytes[20..24].copy_from_slice(&2u32.to_le_bytes());
+        bytes.extend_from_slice(&[1, 2, 3, 0, 4, 5, 6]);
+        let image = Image::from_bytes(&bytes).expect("valid image");
+
+        let rows: Vec<&[u8]> = image.rgb24_rows().expect("uncompressed rows").collect();
+        assert_eq!(rows, [&[1, 2, 3][..], &[4, 5, 6][..]]);
+
+        let image = Image::from_bytes(&bytes[..bytes.len() - 1]).expect("valid image");
+        assert!(image.rgb24_rows().is_none());
+    }
+
+    #[test]
+    fn image_huffman_table_requires_huffman_format() {
+        let mut bytes = make_image_header(2, 11).to_vec();
+        bytes.resize(Image::LENGTH + HuffmanTable::LENGTH, 0);
+        bytes[Image::LENGTH..Image::LENGTH + 4].copy_from_slice(&(1u32 << 27).to_le_bytes());
+
+        let image = Image::from_bytes(&bytes).expect("valid image");
+        let table = image.huffman_table().expect("valid table");
+        assert_eq!(
+            table.code(0).map(|code| (code.length(), code.code())),
+            Some((1, 0))
+        );
+        let image = Image::from_bytes(&bytes[..bytes.len() - 1]).expect("valid image");
+        assert!(image.huffman_table().is_none());
+
+        bytes[12..16].copy_from_slice(&3u32.to_le_bytes());
+        let image = Image::from_bytes(&bytes).expect("valid image");
+        assert!(image.huffman_table().is_none());
+    }
+
+    #[test]
+    fn image_decode_into_packs_rows() {
+        let mut bytes = make_image_header(2, 3).to_vec();
+        bytes[16..20].copy_from_slice(&1u32.to_le_bytes());
+        bytes[20..24].copy_from_slice(&2u32.to_le_bytes());
+        bytes.extend_from_slice(&[1, 2, 3, 0, 4, 5, 6]);
+        let image = Image::from_bytes(&bytes).expect("valid image");
+
+        let mut out = [0xAAu8; 8];
+        assert_eq!(image.decode_into(&bytes, &mut out).expect("decodable"), 6);
+        assert_eq!(out, [1, 2, 3, 4, 5, 6, 0xAA, 0xAA]);
+
+        let err = image.decode_into(&bytes, &mut out[..5]).unwrap_err();
+        match err {
+            X3FError::BufferTooSmall { required: 6 } => {},
+            other => panic!("expected BufferTooSmall, got {other:?}"),
+        }
+        let err = image
+            .decode_into(&bytes[..bytes.len() - 1], &mut out)
+            .unwrap_err();
+        match err {
+            X3FError::TooShort => {},
+            other => panic!("expected TooShort, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn image_decode_into_rejects_compressed_data() {
+        let bytes = make_image_header(2, 18);
+        let image = Image::from_bytes(&bytes).expect("valid image");
+        let err = image.decode_into(&bytes, &mut []).unwrap_err();
+        match err {
+            X3FError::UnsupportedDataFormat => {},
+            other => panic!("expected UnsupportedDataFormat, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn image_row_stride_follows_format() {
+        let mut bytes = make_image_header(2, 3);
+        bytes[16..20].copy_from_slice(&5u32.to_le_bytes());
+        let stride = |bytes: &[u8]| Image::from_bytes(bytes).expect("valid image").row_stride();
+        assert_eq!(stride(&bytes), Some(16));
+
+        bytes[24..28].copy_from_slice(&20u32.to_le_bytes());
+        assert_eq!(stride(&bytes), Some(20));
+
+        bytes[12..16].copy_from_slice(&11u32.to_le_bytes());
+        assert_eq!(stride(&bytes), None);
+
+        bytes[12..16].copy_from_slice(&7u32.to_le_bytes());
+        assert_eq!(stride(&bytes), Some(20));
+        bytes[24..28].copy_from_slice(&0u32.to_le_bytes());
+        assert_eq!(stride(&bytes), None);
+    }
+
+    #[test]
+    fn data_format_all_known_excludes_reserved() {
+        let known = DataFormat::all_known();
+        assert_eq!(
+            known
+                .iter()
+                .map(|format| format.as_u32())
+                .collect::<Vec<_>>(),
+            [3, 11, 18]
+        );
+        assert!(
+            known
+                .iter()
+                .all(|format| !matches!(format, DataFormat::Reserved(_)))
+        );
+        assert_eq!(
+            known
+                .iter()
+                .filter(|format| format.is_decodable())
+                .collect::<Vec<_>>(),
+            [&DataFormat::UncompressedRgb24]
+        );
+    }
+
+    #[test]
+    fn data_format_description_names_each_format() {
+        assert_eq!(
+            DataFormat::UncompressedRgb24.description(),
+            "uncompressed 24-bit RGB"
+        );
+        assert_eq!(
+            DataFormat::HuffmanDpcm888.description(),
+            "Huffman-encoded DPCM RGB"
+        );
+        assert_eq!(DataFormat::Jpeg.description(), "JPEG-compressed RGB");
+        assert_eq!(DataFormat::Reserved(30).description(), "reserved");
+    }
+
+    #[test]
+    fn data_format_round_trips_u32() {
+        for value in [3, 11, 18, 0, 30] {
+            assert_eq!(DataFormat::from_u32(value).as_u32(), value);
+            assert_eq!(ImageType::from_u32(value).as_u32(), value);
+        }
+    }
+
+    #[test]
+    fn from_section_bytes_rejects_mismatched_identifier() {
+        let prop_bytes = make_prop(&[("ISO", "100")]);
+        let image_bytes = make_image_header(2, 18);
+        assert!(Prop::from_section_bytes(&prop_bytes).is_ok());
+        assert!(Image::from_section_bytes(&image_bytes).is_ok());
+
+        let err = Prop::from_section_bytes(&image_bytes).unwrap_err();
+        match err {
+            X3FError::InvalidSectionIdentifier { expected, found } => {
+                assert_eq!(&expected, b"SECp");
+                assert_eq!(&found, b"SECi");
+            },
+            other => panic!("expected InvalidSectionIdentifier, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn verify_identifier_compares_expected_magic() {
+        let prop_bytes = make_prop(&[("ISO", "100")]);
+        let image_bytes = make_image_header(2, 18);
+        assert!(
+            Prop::from_bytes(&prop_bytes)
+                .expect("valid prop")
+                .verify_identifier()
+        );
+        assert!(
+            Image::from_bytes(&image_bytes)
+                .expect("valid image")
+                .verify_identifier()
+        );
+        assert!(
+            !Prop::from_bytes(&image_bytes)
+                .expect("valid prop")
+                .verify_identifier()
+        );
+        assert!(
+            !Image::from_bytes(&prop_bytes)
+                .expect("valid image")
+                .verify_identifier()
+        );
+    }
+
+    #[test]
+    fn camf_payload_follows_identifier() {
+        let bytes = *b"SECc\x01\x02\x03";
+        let camf = Camf::from_bytes(&bytes).expect("valid camf");
+        assert_eq!(camf.section_identifier(), b"SECc");
+        assert_eq!(camf.payload(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn camf_from_section_bytes_checks_identifier() {
+        assert!(Camf::from_section_bytes(b"SECc").is_ok());
+        let err = Camf::from_section_bytes(b"SECp").unwrap_err();
+        match err {
+            X3FError::InvalidSectionIdentifier { expected, found } => {
+                assert_eq!(&expected, b"SECc");
+                assert_eq!(&found, b"SECp");
+            },
+            other => panic!("expected InvalidSectionIdentifier, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn camf_blocks_with_offset_counts_from_section_start() {
+        let mut bytes = std::vec![0u8; Camf::HEADER_LENGTH];
+        bytes[0..4].copy_from_slice(b"SECc");
+        for (kind, size) in [(b"CMbT", 24u32), (b"CMbP", 20)] {
+            let start = bytes.len();
+            bytes.extend_from_slice(kind);
+            bytes.extend_from_slice(&[0; 4]);
+            bytes.extend_from_slice(&size.to_le_bytes());
+            bytes.resize(start + usize::try_from(size).expect("size fits"), 0);
+        }
+        let camf = Camf::from_bytes(&bytes).expect("valid camf");
+
+        let offsets: Vec<(usize, u8)> = camf
+            .blocks_with_offset()
+            .map(|(offset, block)| (offset, block.kind()))
+            .collect();
+        assert_eq!(offsets, [(28, b'T'), (52, b'P')]);
+        assert_eq!(&bytes[52..56], b"CMbP");
+    }
+
     #[test]
     fn camf_from_bytes_rejects_short_input() {
         let bytes = std::vec![0u8; Camf::LENGTH - 1];
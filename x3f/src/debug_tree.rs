@@ -0,0 +1,102 @@
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{SectionData, X3F};
+
+impl X3F<'_> {
+    /// Formats the file structure as an indented tree, for exploring files by hand.
+    ///
+    /// Lists the header fields, the extended header if present, and each directory entry
+    /// with a one-line summary of its parsed section. The layout is meant for reading and
+    /// may change between releases.
+    #[must_use]
+    pub fn debug_tree(&self) -> String {
+        let mut tree = String::new();
+        // Writing to a `String` cannot fail.
+        let _ = self.write_debug_tree(&mut tree);
+        tree
+    }
+
+    fn write_debug_tree(
+        &self,
+        out: &mut String,
+    ) -> core::fmt::Result {
+        let header = self.header();
+        writeln!(out, "X3F ({} bytes)", self.as_bytes().len())?;
+        writeln!(out, "  Header")?;
+        writeln!(out, "    Version: {}", header.version())?;
+        writeln!(
+            out,
+            "    Dimensions: {}x{}",
+            header.image_columns_u32(),
+            header.image_rows_u32()
+        )?;
+        writeln!(out, "    Rotation: {}", header.rotation_enum().as_u32())?;
+
+        if let Some(extended) = self.extended_header() {
+            let label = extended.white_balance_label();
+            writeln!(out, "  Extended header")?;
+            writeln!(out, "    White balance: {}", String::from_utf8_lossy(label))?;
+        }
+
+        writeln!(
+            out,
+            "  Directory at {} ({} entries)",
+            self.directory_offset(),
+            self.directory().entries().count()
+        )?;
+        for (index, (entry, data)) in self.sections().enumerate() {
+            writeln!(
+                out,
+                "    [{index}] {} offset={} length={}",
+                entry.entry_type_fourcc(),
+                entry.data_offset_u32(),
+                entry.data_length_u32()
+            )?;
+            match data {
+                Some(SectionData::Prop(prop)) => {
+                    writeln!(out, "      Properties: {}", prop.entry_count())?;
+                },
+                Some(SectionData::Image(image) | SectionData::Ima2(image)) => writeln!(
+                    out,
+                    "      Image: {}x{} {:?} {:?}",
+                    image.image_columns_u32(),
+                    image.image_rows_u32(),
+                    image.image_type(),
+                    image.data_format_enum()
+                )?,
+                Some(SectionData::Camf(camf)) => {
+                    writeln!(out, "      CAMF blocks: {}", camf.blocks().count())?;
+                },
+                None => writeln!(out, "      (not decoded)")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{make_prop, make_x3f};
+
+    #[test]
+    fn debug_tree_lists_entries_with_summaries() {
+        let prop = make_prop(&[("ISO", "100")]);
+        let bytes = make_x3f(&[(b"PROP", &prop), (b"XYZW", &[0; 4])]);
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let tree = x3f.debug_tree();
+        let lines: alloc::vec::Vec<&str> = tree.lines().collect();
+        assert_eq!(lines[1], "  Header");
+        assert!(
+            lines
+                .iter()
+                .all(|line| !line.starts_with("  Extended header"))
+        );
+        assert!(tree.contains("    [0] PROP offset=40 length="));
+        assert!(tree.contains("      Properties: 1\n"));
+        assert!(tree.contains("    [1] XYZW offset="));
+        assert!(tree.ends_with("      (not decoded)\n"));
+    }
+}
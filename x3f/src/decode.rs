@@ -0,0 +1,228 @@
+use core::fmt;
+
+use crate::byte_order::read_u32_le;
+use crate::debug_helper::TruncatedBytes;
+
+/// MSB-first bit cursor over a byte slice, as used by Huffman-encoded image data.
+///
+/// Every read returns `None` without advancing when fewer bits remain than requested.
+#[derive(Clone)]
+pub struct BitReader<'a> {
+    bytes: &'a [u8],
+    bit_pos: usize,
+}
+
+impl fmt::Debug for BitReader<'_> {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("BitReader")
+            .field("bytes", &TruncatedBytes(self.bytes))
+            .field("bit_pos", &self.bit_pos)
+            .finish()
+    }
+}
+
+impl<'a> BitReader<'a> {
+    #[must_use]
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, bit_pos: 0 }
+    }
+
+    /// Returns the number of bits consumed so far.
+    #[must_use]
+    pub fn bit_position(&self) -> usize {
+        self.bit_pos
+    }
+
+    /// Returns the number of unread bits, saturating at `usize::MAX` for buffers whose bit
+    /// length does not fit in a `usize`.
+    #[must_use]
+    pub fn bits_remaining(&self) -> usize {
+        self.bytes
+            .len()
+            .saturating_mul(8)
+            .saturating_sub(self.bit_pos)
+    }
+
+    pub fn read_bit(&mut self) -> Option<bool> {
+        let byte = self.bytes.get(self.bit_pos / 8)?;
+        let bit = (byte >> (7 - self.bit_pos % 8)) & 1 == 1;
+        self.bit_pos += 1;
+        Some(bit)
+    }
+
+    /// Reads `n` bits, most significant first. Returns `None` if `n > 32`.
+    pub fn read_bits(
+        &mut self,
+        n: u32,
+    ) -> Option<u32> {
+        if n > 32 || self.bits_remaining() < n as usize {
+            return None;
+        }
+
+        let mut value = 0u64;
+        for _ in 0..n {
+            value = (value << 1) | u64::from(self.read_bit()?);
+        }
+        u32::try_from(value).ok()
+    }
+}
+
+/// Code assigned to one byte value in a [`HuffmanTable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HuffmanCode {
+    length: u8,
+    code: u32,
+}
+
+impl HuffmanCode {
+    /// Returns the code length in bits.
+    #[must_use]
+    pub const fn length(self) -> u8 {
+        self.length
+    }
+
+    /// Returns the code, right-aligned in `length` bits.
+    #[must_use]
+    pub const fn code(self) -> u32 {
+        self.code
+    }
+}
+
+/// Huffman code table of a `HuffmanDpcm888` image section.
+///
+/// # Structure
+///
+/// | Offset | Length | Item | Notes |
+/// | --- | --- | --- | --- |
+/// | 0 | 4 × 256 | Codes | One entry per byte value. Bits 31–27 hold the code length, bits 26–0 the code. A length of 0 marks an unused value. |
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HuffmanTable {
+    codes: [Option<HuffmanCode>; 256],
+}
+
+impl HuffmanTable {
+    /// Size of the table in bytes.
+    pub const LENGTH: usize = 4 * 256;
+
+    /// Parses a table from the start of `bytes`.
+    ///
+    /// Returns `None` if `bytes` is too short, a code does not fit in its length, or no
+    /// value has a code.
+    #[must_use]
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut codes = [None; 256];
+        for (index, slot) in codes.iter_mut().enumerate() {
+            let entry = read_u32_le(bytes, index * 4)?;
+            let length = (entry >> 27) as u8;
+            let code = entry & 0x07FF_FFFF;
+            if length == 0 {
+                continue;
+            }
+            if code >> length != 0 {
+                return None;
+            }
+            *slot = Some(HuffmanCode { length, code });
+        }
+
+        codes.iter().any(Option::is_some).then_some(Self { codes })
+    }
+
+    /// Returns the code for `value`, or `None` if the value is unused.
+    #[must_use]
+    pub fn code(
+        &self,
+        value: u8,
+    ) -> Option<HuffmanCode> {
+        self.codes[usize::from(value)]
+    }
+
+    /// Reads one code from `reader` and returns the value it encodes.
+    ///
+    /// Returns `None` if the bits match no code or the stream ends first.
+    pub fn decode(
+        &self,
+        reader: &mut BitReader<'_>,
+    ) -> Option<u8> {
+        let mut candidate = reader.clone();
+        let mut code = 0u32;
+        for length in 1..=31u8 {
+            code = (code << 1) | u32::from(candidate.read_bit()?);
+            let found = self
+                .codes
+                .iter()
+                .position(|entry| *entry == Some(HuffmanCode { length, code }));
+            if let Some(value) = found {
+                *reader = candidate;
+                return u8::try_from(value).ok();
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table_bytes(codes: &[(u8, u8, u32)]) -> [u8; HuffmanTable::LENGTH] {
+        let mut bytes = [0u8; HuffmanTable::LENGTH];
+        for &(value, length, code) in codes {
+            let entry = (u32::from(length) << 27) | code;
+            let offset = usize::from(value) * 4;
+            bytes[offset..offset + 4].copy_from_slice(&entry.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn huffman_table_decodes_prefix_codes() {
+        let bytes = table_bytes(&[(0, 1, 0b0), (5, 2, 0b10), (200, 3, 0b111)]);
+        let table = HuffmanTable::from_bytes(&bytes).expect("valid table");
+        assert_eq!(table.code(5).map(HuffmanCode::length), Some(2));
+        assert_eq!(table.code(1), None);
+
+        let stream = [0b0101_1101, 0b1100_0000];
+        let mut reader = BitReader::new(&stream);
+        let decoded: [Option<u8>; 5] = core::array::from_fn(|_| table.decode(&mut reader));
+        assert_eq!(decoded, [Some(0), Some(5), Some(200), Some(0), Some(200)]);
+    }
+
+    #[test]
+    fn huffman_table_rejects_malformed_input() {
+        assert!(HuffmanTable::from_bytes(&[0u8; HuffmanTable::LENGTH]).is_none());
+        assert!(HuffmanTable::from_bytes(&table_bytes(&[(0, 1, 0b10)])).is_none());
+        assert!(
+            HuffmanTable::from_bytes(&table_bytes(&[(0, 1, 0)])[..HuffmanTable::LENGTH - 1])
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn read_bits_crosses_byte_boundaries() {
+        let bytes = [0b1011_0011, 0b0101_1110];
+        let mut reader = BitReader::new(&bytes);
+
+        assert_eq!(reader.read_bit(), Some(true));
+        assert_eq!(reader.read_bits(3), Some(0b011));
+        assert_eq!(reader.read_bits(6), Some(0b00_1101));
+        assert_eq!(reader.bit_position(), 10);
+        assert_eq!(reader.read_bits(6), Some(0b01_1110));
+        assert_eq!(reader.read_bit(), None);
+    }
+
+    #[test]
+    fn read_bits_does_not_advance_past_end() {
+        let bytes = [0xFF, 0x00, 0xAA, 0x55, 0x0F];
+        let mut reader = BitReader::new(&bytes);
+
+        assert_eq!(reader.read_bits(33), None);
+        assert_eq!(reader.read_bits(32), Some(0xFF00_AA55));
+        assert_eq!(reader.read_bits(9), None);
+        assert_eq!(reader.bits_remaining(), 8);
+        assert_eq!(reader.read_bits(8), Some(0x0F));
+        assert_eq!(reader.read_bits(0), Some(0));
+    }
+}
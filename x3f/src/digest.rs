@@ -0,0 +1,60 @@
+use alloc::vec::Vec;
+
+use crate::{FourCc, X3F};
+
+const FNV_OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+/// Returns the 64-bit FNV-1a hash of `bytes`. Fast and stable, but not collision resistant.
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+impl X3F<'_> {
+    /// Returns each directory entry's type tag paired with a 64-bit FNV-1a hash of its
+    /// section bytes, in directory order.
+    ///
+    /// Comparing the digests of two versions of a file shows which sections changed. The
+    /// hash is not cryptographic. Sections lying outside the buffer hash as empty.
+    #[must_use]
+    pub fn section_digests(&self) -> Vec<(FourCc, u64)> {
+        self.directory()
+            .entries()
+            .map(|entry| {
+                let bytes = self.section_bytes(&entry).unwrap_or_default();
+                (entry.entry_type_fourcc(), fnv1a_64(bytes))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::make_x3f;
+
+    #[test]
+    fn fnv1a_64_matches_reference_vectors() {
+        assert_eq!(fnv1a_64(b""), 0xCBF2_9CE4_8422_2325);
+        assert_eq!(fnv1a_64(b"a"), 0xAF63_DC4C_8601_EC8C);
+        assert_eq!(fnv1a_64(b"foobar"), 0x8594_4171_F739_67E8);
+    }
+
+    #[test]
+    fn section_digests_detect_changed_sections() {
+        let before = make_x3f(&[(b"PROP", b"SECpabcd"), (b"CAMF", b"SECcabcd")]);
+        let after = make_x3f(&[(b"PROP", b"SECpabcd"), (b"CAMF", b"SECcabce")]);
+        let before = X3F::from_bytes(&before)
+            .expect("valid X3F")
+            .section_digests();
+        let after = X3F::from_bytes(&after)
+            .expect("valid X3F")
+            .section_digests();
+
+        assert_eq!(before[0], after[0]);
+        assert_eq!(before[1].0, FourCc::CAMF);
+        assert_ne!(before[1].1, after[1].1);
+    }
+}
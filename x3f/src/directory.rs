@@ -1,7 +1,10 @@
 use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
 
-use crate::X3FError;
+use crate::byte_order::read_u32_le;
 use crate::debug_helper::TruncatedBytes;
+use crate::{FourCc, X3FError};
 
 /// # Structure
 ///
@@ -10,6 +13,7 @@ use crate::debug_helper::TruncatedBytes;
 /// | 0 | 4 | Section Identifier | Contains `"SECd"` |
 /// | 4 | 4 | Section Version | Section version. Should be 2.0 for now. |
 /// | 8 | 4 | Number of directory entries. | Note: Original spec incorrectly shows offset 4. |
+#[derive(PartialEq, Eq, Hash)]
 pub struct DirectoryRef<'a> {
     bytes: &'a [u8],
 }
@@ -26,6 +30,10 @@ impl fmt::Debug for DirectoryRef<'_> {
 }
 
 impl<'a> DirectoryRef<'a> {
+    pub const SECTION_IDENTIFIER: [u8; 4] = *b"SECd";
+    /// Size of a directory entry as defined by the spec.
+    pub const ENTRY_LENGTH: usize = 12;
+
     /// # Errors
     ///
     /// Returns `X3FError::TooShort` if the input is less than 12 bytes.
@@ -37,6 +45,8 @@ impl<'a> DirectoryRef<'a> {
         Ok(Self { bytes: &bytes[0..] })
     }
 
+    /// Returns the bytes this directory was created from. For a directory obtained from
+    /// [`crate::X3F`], these end just before the directory pointer.
     #[must_use]
     pub fn as_bytes(&self) -> &'a [u8] {
         self.bytes
@@ -47,6 +57,12 @@ impl<'a> DirectoryRef<'a> {
         &self.bytes[0..4]
     }
 
+    /// Returns `true` if the section starts with [`Self::SECTION_IDENTIFIER`].
+    #[must_use]
+    pub fn verify_identifier(&self) -> bool {
+        self.section_identifier() == Self::SECTION_IDENTIFIER
+    }
+
     #[must_use]
     pub fn section_version(&self) -> &'a [u8] {
         &self.bytes[4..8]
@@ -57,18 +73,94 @@ impl<'a> DirectoryRef<'a> {
         &self.bytes[8..12]
     }
 
+    /// Returns the declared number of entries, which may exceed what the bytes hold.
+    #[must_use]
+    pub fn entry_count_u32(&self) -> u32 {
+        read_u32_le(self.entry_count(), 0).unwrap_or(0)
+    }
+
+    /// Returns the declared entry count capped at the number of complete entries present.
+    ///
+    /// Use this rather than [`Self::entry_count_u32`] to size allocations, so a corrupt
+    /// count cannot trigger a large allocation.
+    #[must_use]
+    pub fn bounded_entry_count(&self) -> usize {
+        let physical = self.bytes.len().saturating_sub(12) / self.entry_size();
+        usize::try_from(self.entry_count_u32()).map_or(physical, |declared| declared.min(physical))
+    }
+
+    /// Returns the bytes after the last counted entry, such as padding or vendor data.
+    ///
+    /// Entries are counted with [`Self::bounded_entry_count`], so this is empty when the
+    /// directory ends right after its declared entries. [`Self::entries`] walks physical
+    /// entries and may still yield whole entries from these bytes.
+    #[must_use]
+    pub fn trailing_bytes(&self) -> &'a [u8] {
+        &self.bytes[12 + self.bounded_entry_count() * self.entry_size()..]
+    }
+
+    /// Returns the size of each directory entry in bytes.
+    ///
+    /// Every known file uses [`Self::ENTRY_LENGTH`]; entries are iterated with this stride
+    /// so larger entries in future versions only need this method to change. It is never
+    /// less than [`Self::ENTRY_LENGTH`].
+    #[must_use]
+    pub fn entry_size(&self) -> usize {
+        Self::ENTRY_LENGTH
+    }
+
+    /// Iterates over the directory entries in the order they are stored in the file.
+    ///
+    /// The `n`-th item is always the `n`-th physical entry, so indices from this iterator
+    /// can be used to address entries in the file.
     #[must_use]
     pub fn entries(&self) -> DirectoryEntriesIter<'a> {
         DirectoryEntriesIter {
             bytes: &self.bytes[12..],
             pos: 0,
+            entry_size: self.entry_size(),
         }
     }
+
+    /// Returns the `index`-th entry in physical order, or `None` if there are fewer entries.
+    #[must_use]
+    pub fn entry(
+        &self,
+        index: usize,
+    ) -> Option<DirectoryEntryRef<'a>> {
+        self.entries().nth(index)
+    }
+
+    /// Iterates over entries paired with the offset of their 12 bytes from the start of
+    /// the directory section.
+    ///
+    /// Add [`crate::X3F::directory_offset`] for file positions, or use
+    /// [`crate::X3F::entries_with_position`].
+    pub fn entries_with_position(
+        &self
+    ) -> impl Iterator<Item = (usize, DirectoryEntryRef<'a>)> + use<'a> {
+        let entry_size = self.entry_size();
+        self.entries()
+            .enumerate()
+            .map(move |(index, entry)| (12 + index * entry_size, entry))
+    }
+
+    /// Iterates over the entries whose type is not `tag`, in physical order.
+    pub fn entries_excluding(
+        &self,
+        tag: &[u8; 4],
+    ) -> impl Iterator<Item = DirectoryEntryRef<'a>> + use<'a> {
+        let tag = *tag;
+        self.entries()
+            .filter(move |entry| entry.entry_type() != tag)
+    }
 }
 
+#[derive(Clone)]
 pub struct DirectoryEntriesIter<'a> {
     bytes: &'a [u8],
     pos: usize,
+    entry_size: usize,
 }
 
 impl fmt::Debug for DirectoryEntriesIter<'_> {
@@ -79,6 +171,7 @@ impl fmt::Debug for DirectoryEntriesIter<'_> {
         f.debug_struct("DirectoryEntriesIter")
             .field("bytes", &TruncatedBytes(self.bytes))
             .field("pos", &self.pos)
+            .field("entry_size", &self.entry_size)
             .finish()
     }
 }
@@ -87,11 +180,11 @@ impl<'a> Iterator for DirectoryEntriesIter<'a> {
     type Item = DirectoryEntryRef<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.pos + 12 <= self.bytes.len() {
+        if self.pos + self.entry_size <= self.bytes.len() {
             let entry = DirectoryEntryRef {
-                bytes: &self.bytes[self.pos..self.pos + 12],
+                bytes: &self.bytes[self.pos..self.pos + self.entry_size],
             };
-            self.pos += 12;
+            self.pos += self.entry_size;
             Some(entry)
         } else {
             None
@@ -99,6 +192,8 @@ impl<'a> Iterator for DirectoryEntriesIter<'a> {
     }
 }
 
+impl FusedIterator for DirectoryEntriesIter<'_> {}
+
 /// # Structure
 ///
 /// | Offset | Length | Item | Notes |
@@ -106,6 +201,7 @@ impl<'a> Iterator for DirectoryEntriesIter<'a> {
 /// | 0 | 4 | Offset from start of file to start of entry's data, in bytes. | Offset must be a multiple of 4, so that the data starts on a 32-bit boundary. |
 /// | 4 | 4 | Length of entry's data, in bytes. |  |
 /// | 8 | 4 | Type of entry. | See below for a list of valid types. |
+#[derive(PartialEq, Eq, Hash)]
 pub struct DirectoryEntryRef<'a> {
     bytes: &'a [u8],
 }
@@ -141,6 +237,31 @@ impl<'a> DirectoryEntryRef<'a> {
     pub fn entry_type(&self) -> &'a [u8] {
         &self.bytes[8..12]
     }
+
+    #[must_use]
+    pub fn entry_type_fourcc(&self) -> FourCc {
+        FourCc::from_ascii(&self.entry_type().try_into().unwrap_or([0u8; 4]))
+    }
+
+    #[must_use]
+    pub fn data_offset_u32(&self) -> u32 {
+        read_u32_le(self.data_offset(), 0).unwrap_or(0)
+    }
+
+    #[must_use]
+    pub fn data_length_u32(&self) -> u32 {
+        read_u32_le(self.data_length(), 0).unwrap_or(0)
+    }
+
+    /// Returns the byte range of the entry's data within the file.
+    ///
+    /// Returns `None` if the offset or end does not fit in `usize`, as on 16-bit targets.
+    #[must_use]
+    pub fn data_range(&self) -> Option<Range<usize>> {
+        let offset = usize::try_from(self.data_offset_u32()).ok()?;
+        let length = usize::try_from(self.data_length_u32()).ok()?;
+        Some(offset..offset.checked_add(length)?)
+    }
 }
 
 #[cfg(test)]
@@ -210,9 +331,88 @@ mod tests {
             prop_assert_eq!(entry.data_offset(), &bytes[0..4]);
             prop_assert_eq!(entry.data_length(), &bytes[4..8]);
             prop_assert_eq!(entry.entry_type(), &bytes[8..12]);
+            prop_assert_eq!(*entry.entry_type_fourcc().as_bytes(), &bytes[8..12]);
+        }
+
+        #[test]
+        fn data_range_matches_offset_and_length(offset in any::<u32>(), length in any::<u32>()) {
+            let mut bytes = [0u8; 12];
+            bytes[0..4].copy_from_slice(&offset.to_le_bytes());
+            bytes[4..8].copy_from_slice(&length.to_le_bytes());
+            let entry = DirectoryEntryRef { bytes: &bytes };
+
+            prop_assert_eq!(entry.data_offset_u32(), offset);
+            prop_assert_eq!(entry.data_length_u32(), length);
+            let expected = usize::try_from(u64::from(offset) + u64::from(length))
+                .ok()
+                .map(|end| offset as usize..end);
+            prop_assert_eq!(entry.data_range(), expected);
         }
     }
 
+    #[test]
+    fn entries_iter_clone_yields_same_remaining_entries() {
+        let mut bytes = [0u8; DIRECTORY_HEADER_SIZE + 3 * DIRECTORY_ENTRY_SIZE];
+        for (i, entry) in bytes[DIRECTORY_HEADER_SIZE..]
+            .chunks_exact_mut(DIRECTORY_ENTRY_SIZE)
+            .enumerate()
+        {
+            entry[0] = u8::try_from(i).expect("index fits in u8");
+        }
+        let dir = DirectoryRef { bytes: &bytes };
+
+        let mut iter = dir.entries();
+        iter.next();
+        let snapshot = iter.clone();
+
+        let remaining: Vec<&[u8]> = iter.map(|entry| entry.as_bytes()).collect();
+        let cloned: Vec<&[u8]> = snapshot.map(|entry| entry.as_bytes()).collect();
+        assert_eq!(remaining.len(), 2);
+        assert_eq!(remaining, cloned);
+    }
+
+    #[test]
+    fn entries_iter_stays_exhausted() {
+        let bytes = [0u8; 24];
+        let dir = DirectoryRef { bytes: &bytes };
+
+        let mut iter = dir.entries();
+        assert!(iter.next().is_some());
+        assert!(iter.next().is_none());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn bounded_entry_count_caps_declared_count() {
+        let mut bytes = [0u8; DIRECTORY_HEADER_SIZE + DIRECTORY_ENTRY_SIZE + 6];
+        bytes[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+        let dir = DirectoryRef { bytes: &bytes };
+
+        assert_eq!(dir.entry_count_u32(), u32::MAX);
+        assert_eq!(dir.bounded_entry_count(), 1);
+
+        bytes[8..12].copy_from_slice(&0u32.to_le_bytes());
+        let dir = DirectoryRef { bytes: &bytes };
+        assert_eq!(dir.bounded_entry_count(), 0);
+    }
+
+    #[test]
+    fn trailing_bytes_follow_counted_entries() {
+        let mut bytes = [0u8; DIRECTORY_HEADER_SIZE + 2 * DIRECTORY_ENTRY_SIZE + 4];
+        bytes[8..12].copy_from_slice(&1u32.to_le_bytes());
+        let dir = DirectoryRef { bytes: &bytes };
+        assert_eq!(dir.trailing_bytes().len(), DIRECTORY_ENTRY_SIZE + 4);
+
+        bytes[8..12].copy_from_slice(&2u32.to_le_bytes());
+        let dir = DirectoryRef { bytes: &bytes };
+        assert_eq!(dir.trailing_bytes(), &[0u8; 4]);
+
+        let dir = DirectoryRef {
+            bytes: &bytes[..DIRECTORY_HEADER_SIZE + 2 * DIRECTORY_ENTRY_SIZE],
+        };
+        assert!(dir.trailing_bytes().is_empty());
+    }
+
     #[test]
     fn entries_iter_handles_partial_entry() {
         // 12 bytes header + 6 bytes (partial entry) = 18 bytes
@@ -223,6 +423,71 @@ mod tests {
         assert_eq!(dir.entries().count(), 0);
     }
 
+    #[test]
+    fn entries_with_position_yields_directory_relative_offsets() {
+        let mut bytes = [0u8; 36];
+        bytes[24..28].copy_from_slice(b"PROP");
+        let dir = DirectoryRef { bytes: &bytes };
+
+        let positions: Vec<usize> = dir
+            .entries_with_position()
+            .map(|(position, entry)| {
+                assert_eq!(&bytes[position..position + 12], entry.as_bytes());
+                position
+            })
+            .collect();
+        assert_eq!(positions, [12, 24]);
+    }
+
+    #[test]
+    fn entries_iter_preserves_physical_order() {
+        let tags = [*b"IMAG", *b"PROP", *b"CAMF", *b"IMA2", *b"AAAA"];
+        let mut bytes = std::vec![0u8; DIRECTORY_HEADER_SIZE];
+        for tag in &tags {
+            bytes.extend_from_slice(&[0u8; 8]);
+            bytes.extend_from_slice(tag);
+        }
+        let dir = DirectoryRef { bytes: &bytes };
+
+        let yielded: Vec<[u8; 4]> = dir
+            .entries()
+            .map(|entry| *entry.entry_type_fourcc().as_bytes())
+            .collect();
+        assert_eq!(yielded, tags);
+    }
+
+    #[test]
+    fn entries_excluding_skips_matching_tag() {
+        let mut bytes = std::vec![0u8; DIRECTORY_HEADER_SIZE];
+        for tag in [b"IMAG", b"PROP", b"IMAG", b"CAMF"] {
+            bytes.extend_from_slice(&[0u8; 8]);
+            bytes.extend_from_slice(tag);
+        }
+        let dir = DirectoryRef { bytes: &bytes };
+
+        let yielded: Vec<FourCc> = dir
+            .entries_excluding(b"IMAG")
+            .map(|entry| entry.entry_type_fourcc())
+            .collect();
+        assert_eq!(yielded, [FourCc::PROP, FourCc::CAMF]);
+    }
+
+    #[test]
+    fn entries_iter_steps_by_entry_size() {
+        let mut bytes = [0u8; 32];
+        bytes[8..12].copy_from_slice(b"PROP");
+        bytes[24..28].copy_from_slice(b"IMAG");
+        let iter = DirectoryEntriesIter {
+            bytes: &bytes,
+            pos: 0,
+            entry_size: 16,
+        };
+
+        let tags: Vec<FourCc> = iter.map(|entry| entry.entry_type_fourcc()).collect();
+        assert_eq!(tags, [FourCc::PROP, FourCc::IMAG]);
+        assert_eq!(DirectoryRef { bytes: &bytes }.entry_size(), 12);
+    }
+
     #[test]
     fn entries_iter_handles_exact_boundary() {
         // 12 bytes header + 12 bytes (1 entry) = 24 bytes
@@ -1,13 +1,18 @@
 use core::fmt;
 
-use crate::X3FError;
 use crate::debug_helper::TruncatedBytes;
+use crate::{DirectoryRef, X3FError};
 
 /// # Structure
 ///
 /// | Offset | Length | Item | Notes |
 /// | --- | --- | --- | --- |
 /// | 0 | 4 | Offset of start of directory section from start of file, in bytes. |  |
+///
+/// The spec defines no other trailing fields. The `"SECd"` identifier and section version
+/// live at the start of the directory section itself; see [`crate::DirectoryRef`]. Files
+/// that repeat them after the offset can be read with [`Self::from_bytes_extended`].
+#[derive(PartialEq, Eq, Hash)]
 pub struct DirectoryPointerRef<'a> {
     bytes: &'a [u8],
 }
@@ -25,6 +30,8 @@ impl fmt::Debug for DirectoryPointerRef<'_> {
 
 impl<'a> DirectoryPointerRef<'a> {
     pub const LENGTH: usize = 4;
+    /// Length of the pointer followed by a `"SECd"` identifier and section version.
+    pub const EXTENDED_LENGTH: usize = 12;
 
     /// # Errors
     ///
@@ -39,6 +46,24 @@ impl<'a> DirectoryPointerRef<'a> {
         })
     }
 
+    /// Like [`Self::from_bytes`], also keeping a trailing `"SECd"` identifier and 4-byte
+    /// section version when they follow the offset.
+    ///
+    /// Falls back to the plain 4-byte form when fewer than
+    /// [`Self::EXTENDED_LENGTH`] bytes are given or the identifier does not match.
+    ///
+    /// # Errors
+    ///
+    /// Returns `X3FError::TooShort` if the input is less than 4 bytes.
+    pub fn from_bytes_extended(bytes: &'a [u8]) -> Result<Self, X3FError> {
+        match bytes.get(0..Self::EXTENDED_LENGTH) {
+            Some(extended) if extended[4..8] == DirectoryRef::SECTION_IDENTIFIER => {
+                Ok(Self { bytes: extended })
+            },
+            _ => Self::from_bytes(bytes),
+        }
+    }
+
     #[must_use]
     pub fn as_bytes(&self) -> &'a [u8] {
         self.bytes
@@ -48,6 +73,33 @@ impl<'a> DirectoryPointerRef<'a> {
     pub fn offset(&self) -> &'a [u8] {
         &self.bytes[0..4]
     }
+
+    /// Returns the trailing `"SECd"` identifier, if read by [`Self::from_bytes_extended`].
+    #[must_use]
+    pub fn section_identifier(&self) -> Option<&'a [u8]> {
+        self.bytes.get(4..8)
+    }
+
+    /// Returns the trailing section version, if read by [`Self::from_bytes_extended`].
+    #[must_use]
+    pub fn section_version(&self) -> Option<&'a [u8]> {
+        self.bytes.get(8..12)
+    }
+}
+
+/// Returns the value to store in the directory pointer of `buffer`: the offset of the last
+/// 4-byte-aligned `"SECd"` identifier.
+///
+/// The last aligned match is used because image data may contain the identifier by chance,
+/// while the directory is written after every other section. Returns `None` if there is
+/// no match or its offset does not fit in a `u32`.
+#[must_use]
+pub fn compute_directory_pointer(buffer: &[u8]) -> Option<u32> {
+    let offset = buffer
+        .chunks_exact(4)
+        .rposition(|chunk| chunk == DirectoryRef::SECTION_IDENTIFIER)?
+        * 4;
+    u32::try_from(offset).ok()
 }
 
 #[cfg(test)]
@@ -55,8 +107,51 @@ mod tests {
     use super::*;
     use proptest::prelude::*;
 
+    use crate::test_util::make_x3f;
+
     const DIRECTORY_POINTER_SIZE: usize = 4;
 
+    #[test]
+    fn compute_directory_pointer_matches_written_pointer() {
+        let bytes = make_x3f(&[(b"IMAG", b"xSECdSECd\0\0\0")]);
+        let pointer = &bytes[bytes.len() - DIRECTORY_POINTER_SIZE..];
+        assert_eq!(
+            compute_directory_pointer(&bytes).map(u32::to_le_bytes),
+            Some(pointer.try_into().expect("4 bytes"))
+        );
+        assert_eq!(compute_directory_pointer(b"FOVb\0SECd"), None);
+    }
+
+    #[test]
+    fn from_bytes_extended_reads_trailer_when_present() {
+        let plain =
+            DirectoryPointerRef::from_bytes_extended(&[1, 0, 0, 0]).expect("4-byte pointer");
+        assert_eq!(plain.as_bytes(), &[1, 0, 0, 0]);
+        assert_eq!(plain.section_identifier(), None);
+        assert_eq!(plain.section_version(), None);
+
+        let mut bytes = [0u8; 14];
+        bytes[0..4].copy_from_slice(&40u32.to_le_bytes());
+        bytes[4..8].copy_from_slice(b"SECd");
+        bytes[8..12].copy_from_slice(&0x0002_0000u32.to_le_bytes());
+        let extended = DirectoryPointerRef::from_bytes_extended(&bytes).expect("12-byte pointer");
+        assert_eq!(extended.offset(), &40u32.to_le_bytes());
+        assert_eq!(extended.section_identifier(), Some(&b"SECd"[..]));
+        assert_eq!(
+            extended.section_version(),
+            Some(&0x0002_0000u32.to_le_bytes()[..])
+        );
+
+        bytes[4..8].copy_from_slice(b"JUNK");
+        let fallback = DirectoryPointerRef::from_bytes_extended(&bytes).expect("4-byte pointer");
+        assert_eq!(fallback.as_bytes(), &bytes[0..4]);
+
+        assert!(matches!(
+            DirectoryPointerRef::from_bytes_extended(&[0; 3]),
+            Err(X3FError::TooShort)
+        ));
+    }
+
     proptest! {
         #[test]
         fn directory_pointer_ref_returns_correct_slices(bytes in prop::collection::vec(any::<u8>(), DIRECTORY_POINTER_SIZE..=DIRECTORY_POINTER_SIZE)) {
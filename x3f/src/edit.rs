@@ -0,0 +1,85 @@
+use crate::{X3F, X3FError};
+
+/// Overwrites the data of the `entry_index`-th directory entry in place.
+///
+/// `new_bytes` must be exactly as long as the entry's declared data length, so the file
+/// layout is unchanged. This covers same-size edits of sections such as PROP or CAMF.
+///
+/// # Errors
+///
+/// Returns any error from [`X3F::from_bytes`] if `buffer` does not parse,
+/// [`X3FError::OutOfBounds`] if there is no such entry or its data lies outside `buffer`,
+/// and [`X3FError::LengthMismatch`] if `new_bytes` differs in length from the entry's data.
+pub fn replace_section_bytes(
+    buffer: &mut [u8],
+    entry_index: usize,
+    new_bytes: &[u8],
+) -> Result<(), X3FError> {
+    let range = {
+        let x3f = X3F::from_bytes(buffer)?;
+        let entry = x3f
+            .directory()
+            .entry(entry_index)
+            .ok_or(X3FError::OutOfBounds)?;
+        entry.data_range().ok_or(X3FError::OutOfBounds)?
+    };
+
+    if new_bytes.len() != range.len() {
+        return Err(X3FError::LengthMismatch {
+            expected: range.len(),
+            found: new_bytes.len(),
+        });
+    }
+
+    buffer
+        .get_mut(range)
+        .ok_or(X3FError::OutOfBounds)?
+        .copy_from_slice(new_bytes);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::make_x3f;
+
+    #[test]
+    fn replace_section_bytes_overwrites_in_place() {
+        let mut bytes = make_x3f(&[(b"CAMF", b"SECcabcd"), (b"PROP", b"SECpwxyz")]);
+        let before = bytes.len();
+
+        replace_section_bytes(&mut bytes, 1, b"SECp1234").expect("same-size replacement");
+
+        assert_eq!(bytes.len(), before);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let entry = x3f.directory().entries().nth(1).expect("second entry");
+        assert_eq!(x3f.section_bytes(&entry), Some(&b"SECp1234"[..]));
+        let entry = x3f.directory().entries().next().expect("first entry");
+        assert_eq!(x3f.section_bytes(&entry), Some(&b"SECcabcd"[..]));
+    }
+
+    #[test]
+    fn replace_section_bytes_rejects_length_mismatch() {
+        let mut bytes = make_x3f(&[(b"CAMF", b"SECcabcd")]);
+        let original = bytes.clone();
+
+        match replace_section_bytes(&mut bytes, 0, b"SECc").unwrap_err() {
+            X3FError::LengthMismatch {
+                expected: 8,
+                found: 4,
+            } => {},
+            other => panic!("expected LengthMismatch, got {other:?}"),
+        }
+        assert_eq!(bytes, original);
+    }
+
+    #[test]
+    fn replace_section_bytes_rejects_missing_entry() {
+        let mut bytes = make_x3f(&[(b"CAMF", b"SECcabcd")]);
+
+        match replace_section_bytes(&mut bytes, 1, b"SECcabcd").unwrap_err() {
+            X3FError::OutOfBounds => {},
+            other => panic!("expected OutOfBounds, got {other:?}"),
+        }
+    }
+}
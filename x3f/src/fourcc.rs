@@ -0,0 +1,70 @@
+use core::fmt;
+
+/// Four-character code identifying a directory entry type or section.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FourCc([u8; 4]);
+
+impl fmt::Debug for FourCc {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "FourCc(\"{self}\")")
+    }
+}
+
+impl fmt::Display for FourCc {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{}", core::ascii::escape_default(byte))?;
+        }
+        Ok(())
+    }
+}
+
+impl FourCc {
+    pub const PROP: Self = Self::from_ascii(b"PROP");
+    pub const IMAG: Self = Self::from_ascii(b"IMAG");
+    pub const IMA2: Self = Self::from_ascii(b"IMA2");
+    pub const CAMF: Self = Self::from_ascii(b"CAMF");
+
+    #[must_use]
+    pub const fn from_ascii(bytes: &[u8; 4]) -> Self {
+        Self(*bytes)
+    }
+
+    /// Returns `None` unless `bytes` is exactly 4 bytes long.
+    #[must_use]
+    pub fn from_slice(bytes: &[u8]) -> Option<Self> {
+        bytes.try_into().ok().map(Self)
+    }
+
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8; 4] {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::format;
+
+    #[test]
+    fn fourcc_display_escapes_non_printable_bytes() {
+        assert_eq!(format!("{}", FourCc::PROP), "PROP");
+        assert_eq!(format!("{}", FourCc::from_ascii(b"A\0B\n")), "A\\x00B\\n");
+    }
+
+    #[test]
+    fn fourcc_from_slice_requires_four_bytes() {
+        assert_eq!(FourCc::from_slice(b"CAMF"), Some(FourCc::CAMF));
+        assert_eq!(FourCc::from_slice(b"CAM"), None);
+        assert_eq!(FourCc::from_slice(b"CAMFX"), None);
+    }
+}
@@ -1,7 +1,8 @@
 use core::fmt;
 
-use crate::X3FError;
+use crate::byte_order::read_u32_le;
 use crate::debug_helper::TruncatedBytes;
+use crate::{Version, X3FError};
 
 /// # Structure
 ///
@@ -31,6 +32,7 @@ use crate::debug_helper::TruncatedBytes;
 /// | 28 | 4 | Image columns | Width of unrotated image |
 /// | 32 | 4 | Image rows | Height of unrotated image |
 /// | 36 | 4 | Rotation | Clockwise rotation: 0, 90, 180, 270 |
+#[derive(PartialEq, Eq, Hash)]
 pub struct HeaderRef<'a> {
     bytes: &'a [u8],
 }
@@ -48,6 +50,9 @@ impl fmt::Debug for HeaderRef<'_> {
 
 impl<'a> HeaderRef<'a> {
     pub const LENGTH: usize = 40;
+    /// Raw file format versions above this value, version 2.0, are followed by an
+    /// [`ExtendedHeaderRef`].
+    pub const EXTENDED_HEADER_VERSION_THRESHOLD: u32 = Version::new(2, 0).raw();
 
     /// # Errors
     ///
@@ -62,6 +67,12 @@ impl<'a> HeaderRef<'a> {
         })
     }
 
+    /// Infallible counterpart to [`Self::from_bytes`] for a buffer of exactly 40 bytes.
+    #[must_use]
+    pub const fn from_array(bytes: &'a [u8; 40]) -> Self {
+        Self { bytes }
+    }
+
     #[must_use]
     pub fn as_bytes(&self) -> &'a [u8] {
         self.bytes
@@ -72,6 +83,12 @@ impl<'a> HeaderRef<'a> {
         &self.bytes[0..4]
     }
 
+    /// Returns the file type identifier as an array, for comparisons and error reports.
+    #[must_use]
+    pub fn file_type_identifier_bytes(&self) -> [u8; 4] {
+        self.file_type_identifier().try_into().unwrap_or_default()
+    }
+
     #[must_use]
     pub fn file_format_version(&self) -> &'a [u8] {
         &self.bytes[4..8]
@@ -82,6 +99,13 @@ impl<'a> HeaderRef<'a> {
         &self.bytes[8..24]
     }
 
+    /// Returns the unique identifier as an integer, reading its 16 bytes as little-endian
+    /// so the first byte is the least significant.
+    #[must_use]
+    pub fn unique_identifier_u128(&self) -> u128 {
+        u128::from_le_bytes(self.unique_identifier().try_into().unwrap_or_default())
+    }
+
     #[must_use]
     pub fn mark_bits(&self) -> &'a [u8] {
         &self.bytes[24..28]
@@ -101,9 +125,189 @@ impl<'a> HeaderRef<'a> {
     pub fn rotation(&self) -> &'a [u8] {
         &self.bytes[36..40]
     }
+
+    #[must_use]
+    pub fn version(&self) -> Version {
+        Version::from_raw(read_u32_le(self.file_format_version(), 0).unwrap_or(0))
+    }
+
+    /// Returns `true` if the raw file format version exceeds
+    /// [`Self::EXTENDED_HEADER_VERSION_THRESHOLD`].
+    #[must_use]
+    pub fn requires_extended_header(&self) -> bool {
+        self.version().raw() > Self::EXTENDED_HEADER_VERSION_THRESHOLD
+    }
+
+    #[must_use]
+    pub fn image_columns_u32(&self) -> u32 {
+        read_u32_le(self.image_columns(), 0).unwrap_or(0)
+    }
+
+    #[must_use]
+    pub fn image_rows_u32(&self) -> u32 {
+        read_u32_le(self.image_rows(), 0).unwrap_or(0)
+    }
+
+    #[must_use]
+    pub fn rotation_enum(&self) -> Rotation {
+        Rotation::from_u32(read_u32_le(self.rotation(), 0).unwrap_or(0))
+    }
+}
+
+/// Returns whether a file starting with `bytes` is expected to carry an extended header.
+///
+/// Only the 40-byte header is read, so this can be used to branch on the file version
+/// before parsing the whole file with [`crate::X3F::from_bytes`].
+///
+/// # Errors
+///
+/// Returns `X3FError::TooShort` if the input is less than 40 bytes.
+/// Returns `X3FError::InvalidFileType` if the file type identifier is not `"FOVb"`.
+pub fn has_extended_header(bytes: &[u8]) -> Result<bool, X3FError> {
+    let header = HeaderRef::from_bytes(bytes)?;
+    if header.file_type_identifier() != b"FOVb" {
+        return Err(X3FError::InvalidFileType {
+            found: header.file_type_identifier_bytes(),
+        });
+    }
+    Ok(header.requires_extended_header())
+}
+
+/// Clockwise rotation of the image, as stored in the header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rotation {
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+    /// Any value other than 0, 90, 180 or 270.
+    Other(u32),
+}
+
+impl Rotation {
+    #[must_use]
+    pub const fn from_u32(value: u32) -> Self {
+        match value {
+            0 => Self::Deg0,
+            90 => Self::Deg90,
+            180 => Self::Deg180,
+            270 => Self::Deg270,
+            other => Self::Other(other),
+        }
+    }
+
+    #[must_use]
+    pub const fn as_u32(self) -> u32 {
+        match self {
+            Self::Deg0 => 0,
+            Self::Deg90 => 90,
+            Self::Deg180 => 180,
+            Self::Deg270 => 270,
+            Self::Other(value) => value,
+        }
+    }
+}
+
+/// Meaning of an extended data value, as given by its type identifier.
+///
+/// Every defined adjustment is stored as a 32-bit float.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExtendedDataType {
+    /// `0`: the slot is unused.
+    Unused,
+    /// `1`
+    ExposureAdjust,
+    /// `2`
+    ContrastAdjust,
+    /// `3`
+    ShadowAdjust,
+    /// `4`
+    HighlightAdjust,
+    /// `5`
+    SaturationAdjust,
+    /// `6`
+    SharpnessAdjust,
+    /// `7`
+    RedAdjust,
+    /// `8`
+    GreenAdjust,
+    /// `9`
+    BlueAdjust,
+    /// `10`
+    FillLightAdjust,
+    /// Any other value; RESERVED by the spec.
+    Reserved(u8),
+}
+
+impl ExtendedDataType {
+    #[must_use]
+    pub const fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Unused,
+            1 => Self::ExposureAdjust,
+            2 => Self::ContrastAdjust,
+            3 => Self::ShadowAdjust,
+            4 => Self::HighlightAdjust,
+            5 => Self::SaturationAdjust,
+            6 => Self::SharpnessAdjust,
+            7 => Self::RedAdjust,
+            8 => Self::GreenAdjust,
+            9 => Self::BlueAdjust,
+            10 => Self::FillLightAdjust,
+            other => Self::Reserved(other),
+        }
+    }
+
+    #[must_use]
+    pub const fn as_u8(self) -> u8 {
+        match self {
+            Self::Unused => 0,
+            Self::ExposureAdjust => 1,
+            Self::ContrastAdjust => 2,
+            Self::ShadowAdjust => 3,
+            Self::HighlightAdjust => 4,
+            Self::SaturationAdjust => 5,
+            Self::SharpnessAdjust => 6,
+            Self::RedAdjust => 7,
+            Self::GreenAdjust => 8,
+            Self::BlueAdjust => 9,
+            Self::FillLightAdjust => 10,
+            Self::Reserved(value) => value,
+        }
+    }
+}
+
+/// Extended data value interpreted according to its [`ExtendedDataType`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExtendedValue {
+    /// The slot's type is [`ExtendedDataType::Unused`].
+    Unused,
+    /// A defined adjustment.
+    Float(f32),
+    /// A RESERVED type, whose encoding is unknown.
+    Raw(u32),
+}
+
+impl ExtendedValue {
+    /// Reinterprets a [`Self::Raw`] value as signed 16.16 fixed-point.
+    ///
+    /// The spec does not define the encoding of RESERVED slots, so this is a reading the
+    /// caller chooses, not a decoding the format guarantees. Returns `None` for
+    /// [`Self::Float`] and [`Self::Unused`].
+    #[must_use]
+    pub fn as_f32_fixed16(&self) -> Option<f32> {
+        let Self::Raw(raw) = self else {
+            return None;
+        };
+        let [int_high, int_low, frac_high, frac_low] = raw.to_be_bytes();
+        let integer = i16::from_be_bytes([int_high, int_low]);
+        let fraction = u16::from_be_bytes([frac_high, frac_low]);
+        Some(f32::from(integer) + f32::from(fraction) / 65536.0)
+    }
 }
 
 /// Extended Header is an optional section that follows Header only in versions 2.1 - 2.2.
+#[derive(PartialEq, Eq, Hash)]
 pub struct ExtendedHeaderRef<'a> {
     bytes: &'a [u8],
 }
@@ -135,6 +339,12 @@ impl<'a> ExtendedHeaderRef<'a> {
         })
     }
 
+    /// Infallible counterpart to [`Self::from_bytes`] for a buffer of exactly 192 bytes.
+    #[must_use]
+    pub const fn from_array(bytes: &'a [u8; 192]) -> Self {
+        Self { bytes }
+    }
+
     #[must_use]
     pub fn as_bytes(&self) -> &'a [u8] {
         self.bytes
@@ -145,6 +355,18 @@ impl<'a> ExtendedHeaderRef<'a> {
         &self.bytes[0..32]
     }
 
+    /// Returns the white balance label up to its first NUL byte, or all 32 bytes if there
+    /// is none.
+    #[must_use]
+    pub fn white_balance_label(&self) -> &'a [u8] {
+        let label = self.white_balance_label_string();
+        let end = label
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(label.len());
+        &label[..end]
+    }
+
     #[must_use]
     pub fn extended_data_types(&self) -> &'a [u8] {
         &self.bytes[32..64]
@@ -154,6 +376,52 @@ impl<'a> ExtendedHeaderRef<'a> {
     pub fn extended_data(&self) -> &'a [u8] {
         &self.bytes[64..192]
     }
+
+    /// Returns the `index`-th of the 32 extended data type identifiers, or `None` if `index >= 32`.
+    #[must_use]
+    pub fn extended_data_type(
+        &self,
+        index: usize,
+    ) -> Option<u8> {
+        self.extended_data_types().get(index).copied()
+    }
+
+    /// Returns the type of the `index`-th extended data value, or `None` if `index >= 32`.
+    #[must_use]
+    pub fn extended_data_type_enum(
+        &self,
+        index: usize,
+    ) -> Option<ExtendedDataType> {
+        self.extended_data_type(index)
+            .map(ExtendedDataType::from_u8)
+    }
+
+    /// Returns the `index`-th extended data value interpreted according to its type,
+    /// or `None` if `index >= 32`.
+    #[must_use]
+    pub fn typed_value(
+        &self,
+        index: usize,
+    ) -> Option<ExtendedValue> {
+        let raw = self.extended_data_value(index)?;
+        Some(match self.extended_data_type_enum(index)? {
+            ExtendedDataType::Unused => ExtendedValue::Unused,
+            ExtendedDataType::Reserved(_) => ExtendedValue::Raw(raw),
+            _ => ExtendedValue::Float(f32::from_bits(raw)),
+        })
+    }
+
+    /// Returns the `index`-th of the 32 extended data values, or `None` if `index >= 32`.
+    #[must_use]
+    pub fn extended_data_value(
+        &self,
+        index: usize,
+    ) -> Option<u32> {
+        if index >= 32 {
+            return None;
+        }
+        read_u32_le(self.extended_data(), index * 4)
+    }
 }
 
 #[cfg(test)]
@@ -177,6 +445,19 @@ mod tests {
             prop_assert_eq!(header.image_columns(), &bytes[28..32]);
             prop_assert_eq!(header.image_rows(), &bytes[32..36]);
             prop_assert_eq!(header.rotation(), &bytes[36..40]);
+            prop_assert_eq!(header.version().raw(), u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]));
+            prop_assert_eq!(header.image_columns_u32(), u32::from_le_bytes([bytes[28], bytes[29], bytes[30], bytes[31]]));
+            prop_assert_eq!(header.image_rows_u32(), u32::from_le_bytes([bytes[32], bytes[33], bytes[34], bytes[35]]));
+            prop_assert_eq!(header.rotation_enum().as_u32(), u32::from_le_bytes([bytes[36], bytes[37], bytes[38], bytes[39]]));
+        }
+
+        #[test]
+        fn requires_extended_header_follows_threshold(raw in any::<u32>()) {
+            let mut bytes = [0u8; HEADER_SIZE];
+            bytes[4..8].copy_from_slice(&raw.to_le_bytes());
+            let header = HeaderRef { bytes: &bytes };
+
+            prop_assert_eq!(header.requires_extended_header(), raw > 0x0002_0000);
         }
 
         #[test]
@@ -188,5 +469,150 @@ mod tests {
             prop_assert_eq!(extended.extended_data_types(), &bytes[32..64]);
             prop_assert_eq!(extended.extended_data(), &bytes[64..192]);
         }
+
+        #[test]
+        fn extended_data_type_reads_each_slot(bytes in prop::collection::vec(any::<u8>(), EXTENDED_HEADER_SIZE..=EXTENDED_HEADER_SIZE), index in 0usize..40) {
+            let extended = ExtendedHeaderRef { bytes: &bytes };
+
+            let expected = (index < 32).then(|| bytes[32 + index]);
+            prop_assert_eq!(extended.extended_data_type(index), expected);
+        }
+
+        #[test]
+        fn extended_data_value_reads_each_slot(bytes in prop::collection::vec(any::<u8>(), EXTENDED_HEADER_SIZE..=EXTENDED_HEADER_SIZE), index in 0usize..40) {
+            let extended = ExtendedHeaderRef { bytes: &bytes };
+
+            let expected = (index < 32).then(|| {
+                let start = 64 + index * 4;
+                u32::from_le_bytes([bytes[start], bytes[start + 1], bytes[start + 2], bytes[start + 3]])
+            });
+            prop_assert_eq!(extended.extended_data_value(index), expected);
+        }
+
+        #[test]
+        fn extended_data_type_round_trips_u8(value in any::<u8>()) {
+            prop_assert_eq!(ExtendedDataType::from_u8(value).as_u8(), value);
+        }
+    }
+
+    #[test]
+    fn headers_and_rotations_work_as_set_keys() {
+        extern crate std;
+        use std::collections::HashSet;
+
+        let first = [1u8; HEADER_SIZE];
+        let copy = [1u8; HEADER_SIZE];
+        let other = [2u8; HEADER_SIZE];
+        let headers: HashSet<HeaderRef<'_>> = [&first, &copy, &other]
+            .into_iter()
+            .map(HeaderRef::from_array)
+            .collect();
+        assert_eq!(headers.len(), 2);
+
+        let rotations: HashSet<Rotation> = [0, 90, 0, 45, 45]
+            .into_iter()
+            .map(Rotation::from_u32)
+            .collect();
+        assert_eq!(rotations.len(), 3);
+    }
+
+    #[test]
+    fn unique_identifier_u128_is_little_endian() {
+        let mut bytes = [0u8; HEADER_SIZE];
+        bytes[8..24].copy_from_slice(&[
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD,
+            0xEE, 0xFF,
+        ]);
+        let header = HeaderRef::from_array(&bytes);
+        assert_eq!(
+            header.unique_identifier_u128(),
+            0xFFEE_DDCC_BBAA_9988_7766_5544_3322_1100
+        );
+    }
+
+    #[test]
+    fn as_f32_fixed16_reads_raw_values() {
+        assert_eq!(ExtendedValue::Raw(0x0001_8000).as_f32_fixed16(), Some(1.5));
+        assert_eq!(
+            ExtendedValue::Raw(0xFFFF_C000).as_f32_fixed16(),
+            Some(-0.25)
+        );
+        assert_eq!(ExtendedValue::Float(1.5).as_f32_fixed16(), None);
+        assert_eq!(ExtendedValue::Unused.as_f32_fixed16(), None);
+    }
+
+    #[test]
+    fn white_balance_label_stops_at_first_nul() {
+        let mut bytes = [0u8; EXTENDED_HEADER_SIZE];
+        bytes[0..4].copy_from_slice(b"Auto");
+        bytes[5] = b'x';
+        assert_eq!(
+            ExtendedHeaderRef { bytes: &bytes }.white_balance_label(),
+            b"Auto"
+        );
+
+        bytes[0..32].fill(b'A');
+        assert_eq!(
+            ExtendedHeaderRef { bytes: &bytes }.white_balance_label(),
+            &[b'A'; 32]
+        );
+    }
+
+    #[test]
+    fn from_array_matches_from_bytes() {
+        let mut header_bytes = [0u8; HEADER_SIZE];
+        header_bytes[0..4].copy_from_slice(b"FOVb");
+        header_bytes[28..32].copy_from_slice(&5424u32.to_le_bytes());
+        let from_array = HeaderRef::from_array(&header_bytes);
+        let from_bytes = HeaderRef::from_bytes(&header_bytes).expect("40 bytes");
+        assert_eq!(from_array.as_bytes(), from_bytes.as_bytes());
+        assert_eq!(from_array.image_columns_u32(), 5424);
+
+        let mut extended_bytes = [0u8; EXTENDED_HEADER_SIZE];
+        extended_bytes[0..4].copy_from_slice(b"Auto");
+        let from_array = ExtendedHeaderRef::from_array(&extended_bytes);
+        let from_bytes = ExtendedHeaderRef::from_bytes(&extended_bytes).expect("192 bytes");
+        assert_eq!(from_array.as_bytes(), from_bytes.as_bytes());
+    }
+
+    #[test]
+    fn typed_value_interprets_by_type() {
+        let mut bytes = [0u8; EXTENDED_HEADER_SIZE];
+        bytes[32] = 1;
+        bytes[33] = 0;
+        bytes[34] = 200;
+        bytes[64..68].copy_from_slice(&(-0.5f32).to_bits().to_le_bytes());
+        bytes[68..72].copy_from_slice(&7u32.to_le_bytes());
+        bytes[72..76].copy_from_slice(&7u32.to_le_bytes());
+        let extended = ExtendedHeaderRef { bytes: &bytes };
+
+        assert_eq!(
+            extended.extended_data_type_enum(0),
+            Some(ExtendedDataType::ExposureAdjust)
+        );
+        assert_eq!(extended.typed_value(0), Some(ExtendedValue::Float(-0.5)));
+        assert_eq!(extended.typed_value(1), Some(ExtendedValue::Unused));
+        assert_eq!(extended.typed_value(2), Some(ExtendedValue::Raw(7)));
+        assert_eq!(extended.typed_value(32), None);
+    }
+
+    #[test]
+    fn has_extended_header_checks_magic_and_version() {
+        let mut bytes = [0u8; HEADER_SIZE];
+        bytes[0..4].copy_from_slice(b"FOVb");
+        bytes[4..8].copy_from_slice(&Version::new(2, 1).raw().to_le_bytes());
+        assert!(matches!(has_extended_header(&bytes), Ok(true)));
+        bytes[4..8].copy_from_slice(&Version::new(2, 0).raw().to_le_bytes());
+        assert!(matches!(has_extended_header(&bytes), Ok(false)));
+
+        assert!(matches!(
+            has_extended_header(&bytes[..39]),
+            Err(X3FError::TooShort)
+        ));
+        bytes[0..4].copy_from_slice(b"JPEG");
+        assert!(matches!(
+            has_extended_header(&bytes),
+            Err(X3FError::InvalidFileType { found }) if found == *b"JPEG"
+        ));
     }
 }
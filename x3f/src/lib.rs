@@ -1,20 +1,95 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+mod builder;
+mod byte_order;
+mod camf;
+#[cfg(feature = "alloc")]
+mod conformance;
 mod data;
 mod debug_helper;
+#[cfg(feature = "alloc")]
+mod debug_tree;
+mod decode;
+#[cfg(feature = "alloc")]
+mod digest;
 mod directory;
 mod directory_pointer;
+mod edit;
+mod fourcc;
 mod header;
+mod options;
+#[cfg(feature = "alloc")]
+mod parsed_sections;
+#[cfg(feature = "png")]
+mod png;
+mod reader;
+mod section_decoder;
+#[cfg(feature = "std")]
+mod seekable;
+mod sensor;
+#[cfg(feature = "alloc")]
+mod stats;
+#[cfg(feature = "alloc")]
+mod summary;
+#[cfg(test)]
+mod test_util;
+mod trace;
+mod utf16;
+mod version;
+mod white_balance;
 
-pub use crate::data::{Camf, Image, Prop, SectionData};
+#[cfg(feature = "alloc")]
+pub use crate::builder::X3FBuilder;
+pub use crate::byte_order::{BYTE_ORDER, ByteOrder, read_u16_le, read_u32_le};
+pub use crate::camf::{CamfBlock, CamfBlocks, CamfElementType, CamfMatrix, CamfValue};
+#[cfg(feature = "alloc")]
+pub use crate::conformance::{ConformanceCategory, ConformanceIssue, ConformanceLocation};
+pub use crate::data::{Camf, CharacterFormat, DataFormat, Image, ImageType, Prop, SectionData};
+pub use crate::decode::{BitReader, HuffmanCode, HuffmanTable};
 pub use crate::directory::{DirectoryEntriesIter, DirectoryEntryRef, DirectoryRef};
-pub use crate::directory_pointer::DirectoryPointerRef;
-pub use crate::header::{ExtendedHeaderRef, HeaderRef};
+pub use crate::directory_pointer::{DirectoryPointerRef, compute_directory_pointer};
+pub use crate::edit::replace_section_bytes;
+pub use crate::fourcc::FourCc;
+pub use crate::header::{
+    ExtendedDataType, ExtendedHeaderRef, ExtendedValue, HeaderRef, Rotation, has_extended_header,
+};
+pub use crate::options::ParseOptions;
+#[cfg(feature = "alloc")]
+pub use crate::parsed_sections::ParsedSections;
+#[cfg(feature = "png")]
+pub use crate::png::PngError;
+pub use crate::reader::SectionReader;
+pub use crate::section_decoder::{
+    BUILTIN_DECODERS, CamfDecoder, Ima2Decoder, ImagDecoder, PropDecoder, SectionDecoder,
+};
+#[cfg(feature = "std")]
+pub use crate::seekable::{SeekableError, SeekableX3F};
+pub use crate::sensor::SensorGeneration;
+#[cfg(feature = "alloc")]
+pub use crate::stats::{SectionStats, SectionTypeStats, aggregate_section_stats};
+#[cfg(feature = "alloc")]
+pub use crate::summary::{Summary, summarize};
+pub use crate::trace::{ByteRegion, ParseTrace};
+pub use crate::utf16::Utf16Chars;
+pub use crate::version::Version;
+pub use crate::white_balance::WhiteBalanceInfo;
 
 use core::fmt;
+use core::ops::Range;
 
+use crate::data::expected_section_identifier;
 use crate::debug_helper::TruncatedBytes;
 
+/// Bytes occupied by the extended header, when the file has one.
+const EXTENDED_HEADER_RANGE: Range<usize> =
+    HeaderRef::LENGTH..HeaderRef::LENGTH + ExtendedHeaderRef::LENGTH;
+
 /// # Structure
 ///
 /// | Section | Notes |
@@ -29,6 +104,8 @@ pub struct X3F<'a> {
     header: HeaderRef<'a>,
     extended_header: Option<ExtendedHeaderRef<'a>>,
     directory_pointer: DirectoryPointerRef<'a>,
+    directory_pointer_offset: usize,
+    directory_offset: usize,
     directory: DirectoryRef<'a>,
 }
 
@@ -42,6 +119,8 @@ impl fmt::Debug for X3F<'_> {
             .field("header", &self.header)
             .field("extended_header", &self.extended_header)
             .field("directory_pointer", &self.directory_pointer)
+            .field("directory_pointer_offset", &self.directory_pointer_offset)
+            .field("directory_offset", &self.directory_offset)
             .field("directory", &self.directory)
             .finish()
     }
@@ -50,8 +129,83 @@ impl fmt::Debug for X3F<'_> {
 #[derive(Debug)]
 pub enum X3FError {
     TooShort,
-    InvalidFileType,
+    /// The file does not start with `"FOVb"`; `found` holds its first 4 bytes.
+    InvalidFileType {
+        found: [u8; 4],
+    },
     OutOfBounds,
+    /// A section does not start with the identifier its type requires.
+    InvalidSectionIdentifier {
+        expected: [u8; 4],
+        found: [u8; 4],
+    },
+    /// An image is stored in a data format this crate cannot decode.
+    UnsupportedDataFormat,
+    /// An output buffer cannot hold the `required` number of bytes.
+    BufferTooSmall {
+        required: usize,
+    },
+    /// The data of the `index`-th directory entry starts within the header or extended
+    /// header.
+    SectionInHeader {
+        index: usize,
+    },
+    /// Replacement data is `found` bytes long where `expected` bytes are required.
+    LengthMismatch {
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl X3FError {
+    /// Returns a fixed description of the error, usable without formatting machinery.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::TooShort => "input is too short",
+            Self::InvalidFileType { .. } => "file type identifier is not \"FOVb\"",
+            Self::OutOfBounds => "offset is out of bounds",
+            Self::InvalidSectionIdentifier { .. } => "section identifier does not match its type",
+            Self::UnsupportedDataFormat => "image data format is not supported",
+            Self::BufferTooSmall { .. } => "output buffer is too small",
+            Self::SectionInHeader { .. } => "section data starts within the header",
+            Self::LengthMismatch { .. } => "data length does not match",
+        }
+    }
+}
+
+impl fmt::Display for X3FError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::InvalidFileType { found } => write!(
+                f,
+                "file type identifier is \"{}\", expected \"FOVb\"",
+                FourCc::from_ascii(found),
+            ),
+            Self::InvalidSectionIdentifier { expected, found } => write!(
+                f,
+                "section identifier is \"{}\", expected \"{}\"",
+                FourCc::from_ascii(found),
+                FourCc::from_ascii(expected),
+            ),
+            Self::BufferTooSmall { required } => {
+                write!(f, "output buffer is too small, {required} bytes required")
+            },
+            Self::SectionInHeader { index } => {
+                write!(
+                    f,
+                    "data of directory entry {index} starts within the header"
+                )
+            },
+            Self::LengthMismatch { expected, found } => {
+                write!(f, "data is {found} bytes long, expected {expected}")
+            },
+            _ => f.write_str(self.as_str()),
+        }
+    }
 }
 
 impl<'a> X3F<'a> {
@@ -60,52 +214,181 @@ impl<'a> X3F<'a> {
     /// Returns `X3FError::TooShort` if the input is too small to contain a valid X3F structure.
     /// Returns `X3FError::InvalidFileType` if the file type identifier is not `"FOVb"`.
     pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, X3FError> {
+        Self::from_bytes_with_options(bytes, &ParseOptions::default())
+    }
+
+    /// Parses `bytes` with every check in [`ParseOptions::strict`] enabled.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::from_bytes_with_options`].
+    pub fn from_bytes_strict(bytes: &'a [u8]) -> Result<Self, X3FError> {
+        Self::from_bytes_with_options(bytes, &ParseOptions::strict())
+    }
+
+    /// Parses `bytes`, applying the checks enabled in `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::from_bytes`], and additionally:
+    /// - `X3FError::OutOfBounds` if `validate_offsets` is set and an entry's data lies
+    ///   outside the file.
+    /// - `X3FError::SectionInHeader` if `validate_offsets` is set and an entry's data
+    ///   starts within the header or extended header.
+    /// - `X3FError::InvalidSectionIdentifier` if `validate_identifiers` is set and the
+    ///   directory or a `PROP`, `IMAG`, `IMA2` or `CAMF` section has the wrong identifier.
+    pub fn from_bytes_with_options(
+        bytes: &'a [u8],
+        options: &ParseOptions,
+    ) -> Result<Self, X3FError> {
         if bytes.len() < HeaderRef::LENGTH + DirectoryPointerRef::LENGTH {
             return Err(X3FError::TooShort);
         }
 
+        Self::parse(bytes, bytes.len() - DirectoryPointerRef::LENGTH, *options)
+    }
+
+    /// Parses a file whose directory pointer lives at `pointer_offset` rather than in the
+    /// last 4 bytes, such as files with data appended after the pointer.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::from_bytes`], and `X3FError::OutOfBounds` if the
+    /// pointer does not fit in `bytes` or the directory it points to does not end before it.
+    pub fn from_bytes_with_pointer_offset(
+        bytes: &'a [u8],
+        pointer_offset: usize,
+    ) -> Result<Self, X3FError> {
+        Self::parse(bytes, pointer_offset, ParseOptions::default())
+    }
+
+    fn parse(
+        bytes: &'a [u8],
+        pointer_offset: usize,
+        options: ParseOptions,
+    ) -> Result<Self, X3FError> {
+        if bytes.len() < HeaderRef::LENGTH {
+            return Err(X3FError::TooShort);
+        }
+
         let header = HeaderRef::from_bytes(&bytes[0..HeaderRef::LENGTH])?;
         if header.file_type_identifier() != b"FOVb" {
-            return Err(X3FError::InvalidFileType);
+            return Err(X3FError::InvalidFileType {
+                found: header.file_type_identifier_bytes(),
+            });
         }
 
-        let extended_header =
-            if u32::from_le_bytes(header.file_format_version().try_into().unwrap_or([0u8; 4]))
-                > 0x2000
-            {
-                let range = HeaderRef::LENGTH..HeaderRef::LENGTH + ExtendedHeaderRef::LENGTH;
-                let extended_bytes = bytes.get(range).ok_or(X3FError::TooShort)?;
-                Some(ExtendedHeaderRef::from_bytes(extended_bytes)?)
-            } else {
-                None
-            };
+        let extended_header = if header.requires_extended_header() {
+            match bytes.get(EXTENDED_HEADER_RANGE) {
+                Some(extended_bytes) => Some(ExtendedHeaderRef::from_bytes(extended_bytes)?),
+                None if options.require_extended_header => return Err(X3FError::TooShort),
+                None => None,
+            }
+        } else {
+            None
+        };
 
-        let directory_pointer =
-            DirectoryPointerRef::from_bytes(&bytes[bytes.len() - DirectoryPointerRef::LENGTH..])?;
+        let pointer_end = pointer_offset
+            .checked_add(DirectoryPointerRef::LENGTH)
+            .ok_or(X3FError::OutOfBounds)?;
+        let pointer_bytes = bytes
+            .get(pointer_offset..pointer_end)
+            .ok_or(X3FError::OutOfBounds)?;
+        let directory_pointer = DirectoryPointerRef::from_bytes(pointer_bytes)?;
 
-        let offset = u32::from_le_bytes(
-            directory_pointer
-                .offset()
-                .try_into()
-                .map_err(|_| X3FError::TooShort)?,
-        ) as usize;
-        let directory_bytes = bytes.get(offset..).ok_or(X3FError::OutOfBounds)?;
+        let directory_offset =
+            read_u32_le(directory_pointer.offset(), 0).ok_or(X3FError::TooShort)?;
+        // A u32 offset may not fit in usize on 16-bit targets, and any offset near
+        // usize::MAX must not wrap when the directory header length is added.
+        let directory_offset =
+            usize::try_from(directory_offset).map_err(|_| X3FError::OutOfBounds)?;
+        if directory_offset
+            .checked_add(12)
+            .is_none_or(|end| end > pointer_offset)
+        {
+            return Err(X3FError::OutOfBounds);
+        }
+        let directory_bytes = bytes
+            .get(directory_offset..pointer_offset)
+            .ok_or(X3FError::OutOfBounds)?;
         let directory = DirectoryRef::from_bytes(directory_bytes)?;
 
-        Ok(Self {
+        let x3f = Self {
             bytes,
             header,
             extended_header,
             directory_pointer,
+            directory_pointer_offset: pointer_offset,
+            directory_offset,
             directory,
-        })
+        };
+        x3f.validate(options)?;
+        Ok(x3f)
     }
 
+    fn validate(
+        &self,
+        options: ParseOptions,
+    ) -> Result<(), X3FError> {
+        if options.validate_identifiers && !self.directory.verify_identifier() {
+            return Err(X3FError::InvalidSectionIdentifier {
+                expected: DirectoryRef::SECTION_IDENTIFIER,
+                found: self
+                    .directory
+                    .section_identifier()
+                    .try_into()
+                    .unwrap_or_default(),
+            });
+        }
+
+        for (index, entry) in self.directory.entries().enumerate() {
+            if options.validate_offsets && self.starts_in_header(&entry) {
+                return Err(X3FError::SectionInHeader { index });
+            }
+
+            let Some(section) = self.section_bytes(&entry) else {
+                if options.validate_offsets {
+                    return Err(X3FError::OutOfBounds);
+                }
+                continue;
+            };
+
+            if options.validate_identifiers
+                && let Some(expected) = expected_section_identifier(entry.entry_type_fourcc())
+                && !section.starts_with(&expected)
+            {
+                return Err(X3FError::InvalidSectionIdentifier {
+                    expected,
+                    found: section
+                        .get(0..4)
+                        .and_then(|id| id.try_into().ok())
+                        .unwrap_or_default(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the entire buffer the file was parsed from, including any bytes after the
+    /// directory pointer.
     #[must_use]
     pub fn as_bytes(&self) -> &'a [u8] {
         self.bytes
     }
 
+    /// Returns the bytes of `range` within the buffer, or `None` if it is out of bounds.
+    ///
+    /// Use this with ranges such as [`Self::extended_header_range`] or
+    /// [`DirectoryEntryRef::data_range`] instead of indexing [`Self::as_bytes`] directly.
+    #[must_use]
+    pub fn bytes_range(
+        &self,
+        range: Range<usize>,
+    ) -> Option<&'a [u8]> {
+        self.bytes.get(range)
+    }
+
     #[must_use]
     pub fn header(&self) -> &HeaderRef<'a> {
         &self.header
@@ -126,25 +409,362 @@ impl<'a> X3F<'a> {
         &self.directory
     }
 
+    /// Returns the directory section bytes, from the directory offset up to the directory
+    /// pointer. Bytes after the pointer, and the pointer itself, are excluded.
+    #[must_use]
+    pub fn directory_bytes(&self) -> &'a [u8] {
+        self.directory.as_bytes()
+    }
+
+    /// Returns the [`DirectoryPointerRef::LENGTH`] bytes the directory pointer was read
+    /// from: the last 4 bytes of the file, or those at the offset given to
+    /// [`Self::from_bytes_with_pointer_offset`].
+    #[must_use]
+    pub fn directory_pointer_bytes(&self) -> &'a [u8] {
+        self.directory_pointer.as_bytes()
+    }
+
+    /// Returns the offset of the directory section from the start of the file.
+    #[must_use]
+    pub fn directory_offset(&self) -> usize {
+        self.directory_offset
+    }
+
+    /// Iterates over directory entries paired with the file offset of their 12 bytes,
+    /// for tools that patch entries in place.
+    pub fn entries_with_position(&self) -> impl Iterator<Item = (usize, DirectoryEntryRef<'a>)> {
+        let directory_offset = self.directory_offset;
+        self.directory
+            .entries_with_position()
+            .map(move |(position, entry)| (directory_offset + position, entry))
+    }
+
+    /// Returns the data region between the end of the (extended) header and the directory.
+    ///
+    /// The region may include padding between sections. It is empty if the directory
+    /// starts inside the header.
+    #[must_use]
+    pub fn data_region(&self) -> &'a [u8] {
+        self.bytes
+            .get(self.data_start_offset()..self.directory_offset)
+            .unwrap_or(&[])
+    }
+
+    /// Returns the bytes occupied by the extended header, or `None` if the file has none.
+    ///
+    /// The extended header always directly follows the 40-byte header, so the data region
+    /// starts at the end of this range when present.
+    #[must_use]
+    pub fn extended_header_range(&self) -> Option<Range<usize>> {
+        self.extended_header.as_ref().map(|_| EXTENDED_HEADER_RANGE)
+    }
+
+    /// Returns the offset at which the data region begins: the header length, plus the
+    /// extended header length if the file has one.
+    #[must_use]
+    pub fn data_start_offset(&self) -> usize {
+        self.extended_header_range()
+            .map_or(HeaderRef::LENGTH, |range| range.end)
+    }
+
+    /// Returns `true` if the data of `entry` starts before the data region.
+    fn starts_in_header(
+        &self,
+        entry: &DirectoryEntryRef<'a>,
+    ) -> bool {
+        usize::try_from(entry.data_offset_u32())
+            .is_ok_and(|offset| offset < self.data_start_offset())
+    }
+
+    /// Returns the raw bytes of the section described by `entry`, or `None` if it lies
+    /// outside the buffer.
+    #[must_use]
+    pub fn section_bytes(
+        &self,
+        entry: &DirectoryEntryRef<'a>,
+    ) -> Option<&'a [u8]> {
+        self.bytes.get(entry.data_range()?)
+    }
+
+    /// Decodes the section described by `entry` with [`BUILTIN_DECODERS`].
+    ///
+    /// Returns `None` for unknown entry types and for sections that lie outside the buffer
+    /// or fail to parse. See [`Self::section_data_with`] for custom types.
     #[must_use]
     pub fn section_data(
         &self,
         entry: &DirectoryEntryRef<'a>,
     ) -> Option<SectionData<'a>> {
-        let offset = u32::from_le_bytes(entry.data_offset().try_into().ok()?) as usize;
-        let length = u32::from_le_bytes(entry.data_length().try_into().ok()?) as usize;
-        let entry_type = entry.entry_type();
-
-        let end = offset.checked_add(length)?;
-        let data_bytes = self.bytes.get(offset..end)?;
-
-        match entry_type {
-            b"PROP" => Prop::from_bytes(data_bytes).ok().map(SectionData::Prop),
-            b"IMAG" => Image::from_bytes(data_bytes).ok().map(SectionData::Image),
-            b"IMA2" => Image::from_bytes(data_bytes).ok().map(SectionData::Ima2),
-            b"CAMF" => Camf::from_bytes(data_bytes).ok().map(SectionData::Camf),
+        self.section_data_with(entry, &[])
+    }
+
+    /// Parses the section of the `index`-th directory entry, as [`Self::section_data`] would.
+    ///
+    /// Returns `None` if there is no such entry or its section cannot be decoded.
+    #[must_use]
+    pub fn load_entry(
+        &self,
+        index: usize,
+    ) -> Option<SectionData<'a>> {
+        self.section_data(&self.directory.entry(index)?)
+    }
+
+    /// Iterates over the index and data offset of every directory entry whose offset is
+    /// not a multiple of 4, as the spec requires.
+    pub fn check_alignment(&self) -> impl Iterator<Item = (usize, u32)> {
+        self.directory
+            .entries()
+            .map(|entry| entry.data_offset_u32())
+            .enumerate()
+            .filter(|(_, offset)| !offset.is_multiple_of(4))
+    }
+
+    /// Returns the first directory entry whose data contains the byte at `file_offset`.
+    ///
+    /// Returns `None` if the offset lies in the header, the directory, padding between
+    /// sections, or past the end of every section.
+    #[must_use]
+    pub fn entry_at_offset(
+        &self,
+        file_offset: usize,
+    ) -> Option<DirectoryEntryRef<'a>> {
+        self.directory.entries().find(|entry| {
+            entry
+                .data_range()
+                .is_some_and(|range| range.contains(&file_offset))
+        })
+    }
+
+    /// Returns the sum of every directory entry's declared data length.
+    ///
+    /// Each entry is counted on its own, so overlapping sections contribute their shared
+    /// bytes more than once. See [`DirectoryEntryRef::data_range`] for individual sections.
+    #[must_use]
+    pub fn total_section_bytes(&self) -> u64 {
+        self.directory
+            .entries()
+            .map(|entry| u64::from(entry.data_length_u32()))
+            .sum()
+    }
+
+    /// Returns `true` if both files carry the same 16-byte unique identifier, meaning they
+    /// hold the same capture, such as a backup copy or an edited derivative.
+    #[must_use]
+    pub fn same_image_as(
+        &self,
+        other: &X3F<'_>,
+    ) -> bool {
+        self.header.unique_identifier() == other.header.unique_identifier()
+    }
+
+    /// Returns `true` if both files share the same file format version, unique identifier,
+    /// image columns, image rows and rotation.
+    ///
+    /// Mark bits, the extended header and all sections are ignored, so a file and a copy
+    /// with edited marks or properties compare equal.
+    #[must_use]
+    pub fn metadata_eq(
+        &self,
+        other: &X3F<'_>,
+    ) -> bool {
+        let (a, b) = (&self.header, &other.header);
+        a.version() == b.version()
+            && a.unique_identifier() == b.unique_identifier()
+            && a.image_columns_u32() == b.image_columns_u32()
+            && a.image_rows_u32() == b.image_rows_u32()
+            && a.rotation_enum() == b.rotation_enum()
+    }
+
+    /// Returns the rotation to apply when displaying the image.
+    ///
+    /// This is the header rotation, the authoritative value for the raw image data.
+    /// Embedded JPEG previews may carry their own EXIF orientation, which may already
+    /// include this rotation; it is not consulted here.
+    #[must_use]
+    pub fn effective_rotation(&self) -> Rotation {
+        self.header.rotation_enum()
+    }
+
+    /// Performs a quick sanity pass for triage, returning `true` if all of these hold:
+    ///
+    /// - the file type identifier is `"FOVb"`,
+    /// - the directory starts with `"SECd"`,
+    /// - the declared entry count matches the number of complete entries present,
+    /// - the first `IMAG` or `IMA2` entry, if any, lies within the file.
+    ///
+    /// Section contents are not inspected; see [`Self::from_bytes_strict`] for that.
+    #[must_use]
+    pub fn self_consistent(&self) -> bool {
+        let first_image_fits = self
+            .directory
+            .entries()
+            .find(|entry| matches!(entry.entry_type_fourcc(), FourCc::IMAG | FourCc::IMA2))
+            .is_none_or(|entry| self.section_bytes(&entry).is_some());
+
+        self.header.file_type_identifier() == b"FOVb"
+            && self.directory.verify_identifier()
+            && usize::try_from(self.directory.entry_count_u32())
+                .is_ok_and(|declared| declared == self.directory.entries().count())
+            && first_image_fits
+    }
+
+    /// Iterates over directory entries paired with their parsed section data.
+    ///
+    /// The data is `None` for unknown entry types and for sections that fail to parse.
+    pub fn sections(
+        &self
+    ) -> impl Iterator<Item = (DirectoryEntryRef<'a>, Option<SectionData<'a>>)> {
+        self.directory.entries().map(|entry| {
+            let data = self.section_data(&entry);
+            (entry, data)
+        })
+    }
+
+    /// Iterates over directory entries paired with their index and parsed section data,
+    /// so failures can be reported by entry number.
+    ///
+    /// The data is `None` for unknown entry types and for sections that fail to parse.
+    pub fn indexed_sections(
+        &self
+    ) -> impl Iterator<Item = (usize, DirectoryEntryRef<'a>, Option<SectionData<'a>>)> {
+        self.sections()
+            .enumerate()
+            .map(|(index, (entry, data))| (index, entry, data))
+    }
+
+    /// Iterates over the parsed data of every section this crate understands.
+    ///
+    /// Unlike [`Self::sections`], unknown entry types, out-of-bounds entries and sections
+    /// that fail to parse are skipped rather than yielded as `None`.
+    pub fn decodable_sections(&self) -> impl Iterator<Item = SectionData<'a>> {
+        self.directory
+            .entries()
+            .filter_map(|entry| self.section_data(&entry))
+    }
+
+    /// Returns the first `PROP` section, if any.
+    #[must_use]
+    pub fn prop(&self) -> Option<Prop<'a>> {
+        self.directory
+            .entries()
+            .filter(|entry| entry.entry_type() == b"PROP")
+            .find_map(|entry| match self.section_data(&entry)? {
+                SectionData::Prop(prop) => Some(prop),
+                _ => None,
+            })
+    }
+
+    /// Iterates over the `IMAG` and `IMA2` sections in directory order.
+    ///
+    /// Sections that fail to parse are skipped, as are truncated sections whose declared
+    /// data length runs past the end of the buffer, so every yielded [`Image`] holds its
+    /// full payload.
+    pub fn images(&self) -> impl Iterator<Item = Image<'a>> {
+        self.sections().filter_map(|(_, data)| match data? {
+            SectionData::Image(image) | SectionData::Ima2(image) => Some(image),
             _ => None,
-        }
+        })
+    }
+
+    /// Iterates over the `IMAG` sections in directory order, and the `IMA2` sections too if
+    /// `include_ima2` is `true`.
+    ///
+    /// The spec asks readers to treat `IMA2` like `IMAG`, but writers use `IMA2` only for
+    /// preview data in a format other than uncompressed RGB24, so excluding it leaves the
+    /// primary image sections. Sections that fail to parse are skipped.
+    pub fn images_filtered(
+        &self,
+        include_ima2: bool,
+    ) -> impl Iterator<Item = Image<'a>> {
+        self.sections().filter_map(move |(_, data)| match data? {
+            SectionData::Image(image) => Some(image),
+            SectionData::Ima2(image) if include_ima2 => Some(image),
+            _ => None,
+        })
+    }
+
+    /// Returns the `index`-th image section in directory order, as yielded by
+    /// [`Self::images`].
+    ///
+    /// Files with several images commonly store the thumbnail first, followed by larger
+    /// previews. Returns `None` if there are fewer than `index + 1` images.
+    #[must_use]
+    pub fn image(
+        &self,
+        index: usize,
+    ) -> Option<Image<'a>> {
+        self.images().nth(index)
+    }
+
+    /// Returns `true` if any image section is not processed for preview.
+    ///
+    /// The spec only defines type 2 (processed for preview) and reserves every other value,
+    /// so this treats any other type as raw sensor data.
+    #[must_use]
+    pub fn has_raw_image(&self) -> bool {
+        self.images().any(|image| !image.is_preview())
+    }
+
+    /// Returns the first image section whose data format is `format`.
+    #[must_use]
+    pub fn image_with_format(
+        &self,
+        format: DataFormat,
+    ) -> Option<Image<'a>> {
+        self.images()
+            .find(|image| image.data_format_enum() == format)
+    }
+
+    /// Returns the JPEG stream of the first JPEG image section, with the image header
+    /// stripped.
+    #[must_use]
+    pub fn jpeg_preview(&self) -> Option<&'a [u8]> {
+        self.jpeg_previews().next()
+    }
+
+    /// Iterates over the JPEG streams of every JPEG image section in directory order,
+    /// such as a thumbnail followed by a larger preview.
+    ///
+    /// Each slice is a standalone JPEG stream with the 28-byte image header stripped.
+    pub fn jpeg_previews(&self) -> impl Iterator<Item = &'a [u8]> {
+        self.images()
+            .filter(Image::is_jpeg)
+            .map(|image| image.pixel_data())
+    }
+
+    /// Returns the preview image with the most pixels, for thumbnailers.
+    ///
+    /// Only image sections processed for preview and stored as JPEG or uncompressed RGB24
+    /// are considered. Among those, the largest [`Image::pixel_count`] wins, and ties go
+    /// to the section that comes first in the directory.
+    #[must_use]
+    pub fn best_preview(&self) -> Option<Image<'a>> {
+        self.images()
+            .filter(|image| image.is_preview() && (image.is_jpeg() || image.is_uncompressed_rgb()))
+            .reduce(|best, image| {
+                if image.pixel_count() > best.pixel_count() {
+                    image
+                } else {
+                    best
+                }
+            })
+    }
+
+    /// Iterates over directory entries paired with their type tag.
+    pub fn tagged_sections(&self) -> impl Iterator<Item = (FourCc, DirectoryEntryRef<'a>)> {
+        self.directory
+            .entries()
+            .map(|entry| (entry.entry_type_fourcc(), entry))
+    }
+
+    /// Iterates over the type tags of entries other than `PROP`, `IMAG`, `IMA2` and `CAMF`.
+    ///
+    /// A tag is yielded once per entry, so duplicates appear if several entries share it.
+    pub fn unknown_section_tags(&self) -> impl Iterator<Item = FourCc> {
+        self.tagged_sections().filter_map(|(tag, _)| match tag {
+            FourCc::PROP | FourCc::IMAG | FourCc::IMA2 | FourCc::CAMF => None,
+            other => Some(other),
+        })
     }
 }
 
@@ -153,19 +773,141 @@ mod tests {
     extern crate std;
 
     use super::*;
+    use crate::test_util::{
+        le_u32, make_header, make_image_header, make_prop, make_x3f, make_x3f_with_version,
+    };
     use std::vec::Vec;
 
-    fn make_header(file_format_version: [u8; 4]) -> [u8; HeaderRef::LENGTH] {
-        let mut header = [0u8; HeaderRef::LENGTH];
-        header[0..4].copy_from_slice(b"FOVb");
-        header[4..8].copy_from_slice(&file_format_version);
-        header
+    #[test]
+    fn decodable_sections_skips_unknown_and_invalid_entries() {
+        let prop = make_prop(&[("ISO", "100")]);
+        let bytes = make_x3f(&[(b"ABCD", b"data"), (b"CAMF", b"SEC"), (b"PROP", &prop)]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+
+        let sections: Vec<_> = x3f.decodable_sections().collect();
+        assert_eq!(sections.len(), 1);
+        assert!(matches!(sections[0], SectionData::Prop(_)));
+    }
+
+    #[test]
+    fn effective_rotation_follows_header() {
+        let mut bytes = make_x3f(&[]);
+        bytes[36..40].copy_from_slice(&270u32.to_le_bytes());
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        assert_eq!(x3f.effective_rotation(), Rotation::Deg270);
+    }
+
+    #[test]
+    fn indexed_sections_pairs_entries_with_indices() {
+        let prop = make_prop(&[("ISO", "100")]);
+        let bytes = make_x3f(&[(b"ABCD", b"data"), (b"PROP", &prop)]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+
+        let sections: Vec<(usize, FourCc, bool)> = x3f
+            .indexed_sections()
+            .map(|(index, entry, data)| (index, entry.entry_type_fourcc(), data.is_some()))
+            .collect();
+        assert_eq!(
+            sections,
+            [
+                (0, FourCc::from_ascii(b"ABCD"), false),
+                (1, FourCc::PROP, true)
+            ]
+        );
+    }
+
+    #[test]
+    fn load_entry_parses_indexed_section() {
+        let prop = make_prop(&[("ISO", "100")]);
+        let bytes = make_x3f(&[(b"ABCD", b"data"), (b"PROP", &prop)]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+
+        assert!(x3f.load_entry(0).is_none());
+        assert!(matches!(x3f.load_entry(1), Some(SectionData::Prop(_))));
+        assert!(x3f.load_entry(2).is_none());
+    }
+
+    #[test]
+    fn best_preview_picks_largest_surfaceable_preview() {
+        let sized = |image_type, format, columns: u32, rows: u32| {
+            let mut image = make_image_header(image_type, format);
+            image[16..20].copy_from_slice(&columns.to_le_bytes());
+            image[20..24].copy_from_slice(&rows.to_le_bytes());
+            image
+        };
+        let thumbnail = sized(2, 3, 160, 120);
+        let preview = sized(2, 18, 640, 480);
+        let same_size = sized(2, 3, 480, 640);
+        let huffman = sized(2, 11, 1600, 1200);
+        let raw = sized(30, 18, 4000, 3000);
+        let bytes = make_x3f(&[
+            (b"IMAG", &thumbnail),
+            (b"IMA2", &preview),
+            (b"IMAG", &same_size),
+            (b"IMA2", &huffman),
+            (b"IMAG", &raw),
+        ]);
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let best = x3f.best_preview().expect("preview");
+        assert_eq!(best.as_bytes(), &preview[..]);
+
+        let bytes = make_x3f(&[(b"IMAG", &huffman), (b"IMAG", &raw)]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        assert!(x3f.best_preview().is_none());
+    }
+
+    #[test]
+    fn total_section_bytes_sums_declared_lengths() {
+        let bytes = make_x3f(&[(b"CAMF", b"SECcabcd"), (b"PROP", b"SECp")]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        assert_eq!(x3f.total_section_bytes(), 12);
+    }
+
+    #[test]
+    fn error_display_matches_as_str() {
+        for err in [
+            X3FError::TooShort,
+            X3FError::OutOfBounds,
+            X3FError::UnsupportedDataFormat,
+        ] {
+            assert_eq!(std::format!("{err}"), err.as_str());
+        }
+    }
+
+    #[test]
+    fn invalid_file_type_reports_found_identifier() {
+        let mut bytes = make_x3f(&[]);
+        bytes[0..4].copy_from_slice(b"II*\0");
+
+        let err = X3F::from_bytes(&bytes).unwrap_err();
+        match err {
+            X3FError::InvalidFileType { found } => assert_eq!(&found, b"II*\0"),
+            ref other => panic!("expected InvalidFileType, got {other:?}"),
+        }
+        assert_eq!(
+            std::format!("{err}"),
+            "file type identifier is \"II*\\x00\", expected \"FOVb\""
+        );
+    }
+
+    #[test]
+    fn invalid_section_identifier_display_names_both_identifiers() {
+        let err = X3FError::InvalidSectionIdentifier {
+            expected: *b"SECp",
+            found: *b"SE\0i",
+        };
+        assert_eq!(
+            std::format!("{err}"),
+            "section identifier is \"SE\\x00i\", expected \"SECp\""
+        );
     }
 
     #[test]
     fn from_bytes_rejects_out_of_bounds_directory_offset() {
         let mut bytes = Vec::new();
-        // Use version <= 0x2000 so no extended header is required
+        // Use version 2.0 or older so no extended header is required
         bytes.extend_from_slice(&make_header([0u8; 4]));
         bytes.extend_from_slice(&1000u32.to_le_bytes());
 
@@ -176,6 +918,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn from_bytes_rejects_directory_offset_at_u32_max() {
+        let mut bytes = make_x3f(&[(b"PROP", &[0; 4])]);
+        let pointer_offset = bytes.len() - DirectoryPointerRef::LENGTH;
+        bytes[pointer_offset..].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        match X3F::from_bytes(&bytes).unwrap_err() {
+            X3FError::OutOfBounds => {},
+            other => panic!("expected OutOfBounds, got {other:?}"),
+        }
+        match X3F::from_bytes_with_pointer_offset(&bytes, usize::MAX).unwrap_err() {
+            X3FError::OutOfBounds => {},
+            other => panic!("expected OutOfBounds, got {other:?}"),
+        }
+    }
+
     #[test]
     fn from_bytes_rejects_missing_extended_header() {
         let mut bytes = Vec::new();
@@ -189,13 +947,102 @@ mod tests {
         }
     }
 
+    #[test]
+    fn strict_options_reject_mislabeled_sections() {
+        let bytes = make_x3f(&[(b"PROP", &make_image_header(2, 18))]);
+        assert!(X3F::from_bytes(&bytes).is_ok());
+
+        let err = X3F::from_bytes_strict(&bytes).unwrap_err();
+        match err {
+            X3FError::InvalidSectionIdentifier { expected, found } => {
+                assert_eq!(&expected, b"SECp");
+                assert_eq!(&found, b"SECi");
+            },
+            other => panic!("expected InvalidSectionIdentifier, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn strict_options_reject_out_of_bounds_entries() {
+        let mut bytes = make_x3f(&[(b"XYZW", &[0; 4])]);
+        let length = bytes.len() - DirectoryPointerRef::LENGTH - 8;
+        bytes[length..length + 4].copy_from_slice(&1000u32.to_le_bytes());
+        assert!(X3F::from_bytes(&bytes).is_ok());
+
+        let options = ParseOptions {
+            validate_offsets: true,
+            ..ParseOptions::default()
+        };
+        let err = X3F::from_bytes_with_options(&bytes, &options).unwrap_err();
+        match err {
+            X3FError::OutOfBounds => {},
+            other => panic!("expected OutOfBounds, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn strict_options_reject_entries_inside_header() {
+        let mut bytes = make_x3f(&[(b"XYZW", &[0; 4]), (b"XYZW", &[0; 4])]);
+        let offset = bytes.len() - DirectoryPointerRef::LENGTH - 12;
+        bytes[offset..offset + 4].copy_from_slice(&le_u32(8));
+        assert!(X3F::from_bytes(&bytes).is_ok());
+
+        match X3F::from_bytes_strict(&bytes).unwrap_err() {
+            X3FError::SectionInHeader { index: 1 } => {},
+            other => panic!("expected SectionInHeader, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn lenient_extended_header_option_accepts_missing_extended_header() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&make_header(*b"2.1\0"));
+        bytes.extend_from_slice(b"SECd");
+        bytes.extend_from_slice(&0x0002_0000u32.to_le_bytes());
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes.extend_from_slice(&le_u32(HeaderRef::LENGTH));
+
+        let options = ParseOptions {
+            require_extended_header: false,
+            ..ParseOptions::default()
+        };
+        let x3f = X3F::from_bytes_with_options(&bytes, &options).expect("valid X3F");
+        assert!(x3f.extended_header().is_none());
+    }
+
+    #[test]
+    fn from_bytes_with_pointer_offset_ignores_trailing_data() {
+        let mut bytes = make_x3f(&[(b"PROP", &make_prop(&[("ISO", "100")]))]);
+        let pointer_offset = bytes.len() - DirectoryPointerRef::LENGTH;
+        bytes.extend_from_slice(b"appended metadata");
+
+        assert!(X3F::from_bytes(&bytes).is_err());
+        let x3f = X3F::from_bytes_with_pointer_offset(&bytes, pointer_offset).expect("valid X3F");
+        assert_eq!(x3f.directory().entry_count_u32(), 1);
+        assert!(x3f.prop().is_some());
+    }
+
+    #[test]
+    fn from_bytes_with_pointer_offset_rejects_implausible_pointers() {
+        let bytes = make_x3f(&[]);
+        let pointer_offset = bytes.len() - DirectoryPointerRef::LENGTH;
+
+        for offset in [pointer_offset + 1, usize::MAX, HeaderRef::LENGTH] {
+            let err = X3F::from_bytes_with_pointer_offset(&bytes, offset).unwrap_err();
+            match err {
+                X3FError::OutOfBounds => {},
+                other => panic!("expected OutOfBounds, got {other:?}"),
+            }
+        }
+    }
+
     #[test]
     fn section_data_returns_none_for_out_of_bounds_entry() {
         let mut bytes = Vec::new();
-        // Use version <= 0x2000 so no extended header is required
+        // Use version 2.0 or older so no extended header is required
         bytes.extend_from_slice(&make_header([0u8; 4]));
 
-        let directory_offset = HeaderRef::LENGTH as u32;
+        let directory_offset = u32::try_from(HeaderRef::LENGTH).expect("offset fits in u32");
         let directory_start = bytes.len();
 
         // Directory header (12 bytes)
@@ -219,4 +1066,327 @@ mod tests {
         let entry = x3f.directory().entries().next().expect("entry");
         assert!(x3f.section_data(&entry).is_none());
     }
+
+    #[test]
+    fn data_region_spans_header_end_to_directory() {
+        let bytes = make_x3f(&[(b"PROP", &[1, 2, 3, 4]), (b"CAMF", &[5, 6])]);
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        assert_eq!(x3f.data_region(), &[1, 2, 3, 4, 5, 6, 0, 0]);
+    }
+
+    #[test]
+    fn data_region_is_empty_when_directory_starts_in_header() {
+        let mut bytes = make_x3f(&[]);
+        let len = bytes.len();
+        bytes[len - 4..].copy_from_slice(&8u32.to_le_bytes());
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        assert!(x3f.data_region().is_empty());
+    }
+
+    #[test]
+    fn image_indexes_images_in_directory_order() {
+        let thumbnail = make_image_header(2, 3);
+        let preview = make_image_header(2, 18);
+        let bytes = make_x3f(&[
+            (b"IMAG", &thumbnail),
+            (b"PROP", &make_prop(&[("ISO", "100")])),
+            (b"IMA2", &preview),
+        ]);
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        assert_eq!(x3f.images().count(), 2);
+        assert_eq!(
+            x3f.image(0).map(|image| image.as_bytes()),
+            Some(&thumbnail[..])
+        );
+        assert_eq!(
+            x3f.image(1).map(|image| image.as_bytes()),
+            Some(&preview[..])
+        );
+        assert!(x3f.image(2).is_none());
+    }
+
+    #[test]
+    fn entries_with_position_yields_file_offsets() {
+        let bytes = make_x3f(&[(b"PROP", &[0; 4]), (b"XYZW", &[0; 4])]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+
+        for (position, entry) in x3f.entries_with_position() {
+            assert_eq!(&bytes[position..position + 12], entry.as_bytes());
+        }
+        let positions: Vec<usize> = x3f
+            .entries_with_position()
+            .map(|(position, _)| position)
+            .collect();
+        let first = x3f.directory_offset() + 12;
+        assert_eq!(positions, [first, first + 12]);
+    }
+
+    #[test]
+    fn self_consistent_checks_count_and_first_image() {
+        let bytes = make_x3f(&[(b"IMAG", &make_image_header(2, 3)), (b"XYZW", &[0; 4])]);
+        assert!(
+            X3F::from_bytes(&bytes)
+                .expect("valid X3F")
+                .self_consistent()
+        );
+
+        let count = bytes.len() - DirectoryPointerRef::LENGTH - 2 * 12 - 4;
+        let mut miscounted = bytes.clone();
+        miscounted[count] = 3;
+        assert!(
+            !X3F::from_bytes(&miscounted)
+                .expect("valid X3F")
+                .self_consistent()
+        );
+
+        let mut truncated = bytes.clone();
+        truncated[count + 8] = 0xFF;
+        assert!(
+            !X3F::from_bytes(&truncated)
+                .expect("valid X3F")
+                .self_consistent()
+        );
+    }
+
+    #[test]
+    fn has_raw_image_ignores_previews() {
+        let preview = make_image_header(2, 18);
+        let raw = make_image_header(30, 11);
+        let bytes = make_x3f(&[(b"IMA2", &preview)]);
+        assert!(!X3F::from_bytes(&bytes).expect("valid X3F").has_raw_image());
+
+        let bytes = make_x3f(&[(b"IMA2", &preview), (b"IMAG", &raw)]);
+        assert!(X3F::from_bytes(&bytes).expect("valid X3F").has_raw_image());
+    }
+
+    #[test]
+    fn image_with_format_finds_first_match() {
+        let rgb = make_image_header(2, 3);
+        let first_jpeg = make_image_header(2, 18);
+        let mut second_jpeg = make_image_header(2, 18);
+        second_jpeg[16] = 1;
+        let bytes = make_x3f(&[
+            (b"IMAG", &rgb),
+            (b"IMA2", &first_jpeg),
+            (b"IMA2", &second_jpeg),
+        ]);
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let image = |format| x3f.image_with_format(format).map(|image| image.as_bytes());
+        assert_eq!(image(DataFormat::Jpeg), Some(&first_jpeg[..]));
+        assert_eq!(image(DataFormat::UncompressedRgb24), Some(&rgb[..]));
+        assert_eq!(image(DataFormat::HuffmanDpcm888), None);
+    }
+
+    #[test]
+    fn jpeg_previews_yields_every_jpeg_stream() {
+        let mut thumbnail = make_image_header(2, 18).to_vec();
+        thumbnail.extend_from_slice(&[0xFF, 0xD8, 1, 0xFF, 0xD9]);
+        let mut preview = make_image_header(2, 18).to_vec();
+        preview.extend_from_slice(&[0xFF, 0xD8, 2, 0xFF, 0xD9]);
+        let bytes = make_x3f(&[
+            (b"IMA2", &thumbnail),
+            (b"IMAG", &make_image_header(2, 3)),
+            (b"IMA2", &preview),
+        ]);
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let jpegs: Vec<&[u8]> = x3f.jpeg_previews().collect();
+        assert_eq!(
+            jpegs,
+            [&thumbnail[Image::LENGTH..], &preview[Image::LENGTH..]]
+        );
+        assert_eq!(x3f.jpeg_preview(), Some(&thumbnail[Image::LENGTH..]));
+    }
+
+    #[test]
+    fn images_skip_truncated_sections() {
+        let mut image = make_image_header(2, 3).to_vec();
+        image.extend_from_slice(&[0; 8]);
+        let mut bytes = make_x3f(&[(b"IMAG", &image), (b"IMAG", &image)]);
+        let file_length = le_u32(bytes.len());
+        let length = bytes.len() - DirectoryPointerRef::LENGTH - 8;
+        bytes[length..length + 4].copy_from_slice(&file_length);
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let images: Vec<Image<'_>> = x3f.images().collect();
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].as_bytes(), &image[..]);
+    }
+
+    #[test]
+    fn images_filtered_optionally_skips_ima2() {
+        let rgb = make_image_header(2, 3);
+        let jpeg = make_image_header(2, 18);
+        let bytes = make_x3f(&[(b"IMA2", &jpeg), (b"IMAG", &rgb)]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+
+        let formats = |include_ima2| {
+            x3f.images_filtered(include_ima2)
+                .map(|image| image.data_format_enum())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            formats(true),
+            [DataFormat::Jpeg, DataFormat::UncompressedRgb24]
+        );
+        assert_eq!(formats(false), [DataFormat::UncompressedRgb24]);
+        assert_eq!(x3f.images().count(), 2);
+    }
+
+    #[test]
+    fn directory_bytes_stop_at_directory_pointer() {
+        let mut bytes = make_x3f(&[(b"PROP", &[0; 4])]);
+        let pointer_offset = bytes.len() - DirectoryPointerRef::LENGTH;
+        bytes.extend_from_slice(b"appended");
+
+        let x3f = X3F::from_bytes_with_pointer_offset(&bytes, pointer_offset).expect("valid X3F");
+        assert_eq!(
+            x3f.directory_bytes(),
+            &bytes[x3f.directory_offset()..pointer_offset]
+        );
+        assert_eq!(x3f.directory_bytes().len(), 24);
+        assert_eq!(
+            x3f.directory_pointer_bytes(),
+            &bytes[pointer_offset..pointer_offset + DirectoryPointerRef::LENGTH]
+        );
+    }
+
+    #[test]
+    fn bytes_range_is_bounds_checked() {
+        let bytes = make_x3f(&[]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        assert_eq!(x3f.bytes_range(0..4), Some(&b"FOVb"[..]));
+        assert_eq!(x3f.bytes_range(0..bytes.len()), Some(&bytes[..]));
+        assert_eq!(x3f.bytes_range(0..bytes.len() + 1), None);
+        assert_eq!(x3f.bytes_range(usize::MAX..usize::MAX), None);
+    }
+
+    #[test]
+    fn data_start_offset_follows_extended_header() {
+        let bytes = make_x3f_with_version(0x0002_0001, &[]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        assert_eq!(
+            x3f.data_start_offset(),
+            HeaderRef::LENGTH + ExtendedHeaderRef::LENGTH
+        );
+
+        let bytes = make_x3f(&[]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        assert_eq!(x3f.data_start_offset(), HeaderRef::LENGTH);
+    }
+
+    #[test]
+    fn extended_header_range_follows_header() {
+        let bytes = make_x3f_with_version(0x0002_0001, &[]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        assert_eq!(x3f.extended_header_range(), Some(40..232));
+
+        let bytes = make_x3f(&[]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        assert_eq!(x3f.extended_header_range(), None);
+    }
+
+    #[test]
+    fn directory_pointer_bytes_are_last_bytes_of_file() {
+        let bytes = make_x3f(&[(b"PROP", &[0; 4])]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        assert_eq!(
+            x3f.directory_pointer_bytes(),
+            &bytes[bytes.len() - DirectoryPointerRef::LENGTH..]
+        );
+        assert_eq!(
+            x3f.directory_pointer_bytes(),
+            le_u32(x3f.directory_offset())
+        );
+    }
+
+    #[test]
+    fn metadata_eq_compares_significant_header_fields() {
+        let original = make_x3f(&[(b"PROP", &make_prop(&[("ISO", "100")]))]);
+        let mut copy = make_x3f(&[]);
+        copy[8..24].copy_from_slice(&original[8..24]);
+        copy[24] = 1; // mark bits
+        let x3f = X3F::from_bytes(&original).expect("valid X3F");
+        assert!(x3f.metadata_eq(&X3F::from_bytes(&copy).expect("valid X3F")));
+
+        for offset in [8, 28, 32, 36] {
+            let mut other = original.clone();
+            other[offset] ^= 1;
+            let other = X3F::from_bytes(&other).expect("valid X3F");
+            assert!(!x3f.metadata_eq(&other), "offset {offset}");
+        }
+    }
+
+    #[test]
+    fn same_image_as_compares_unique_identifiers() {
+        let mut original = make_x3f(&[]);
+        original[8..24].copy_from_slice(&[7; 16]);
+        let mut copy = make_x3f(&[(b"PROP", &make_prop(&[("ISO", "100")]))]);
+        copy[8..24].copy_from_slice(&[7; 16]);
+        let x3f = X3F::from_bytes(&original).expect("valid X3F");
+        assert!(x3f.same_image_as(&X3F::from_bytes(&copy).expect("valid X3F")));
+
+        copy[23] ^= 1;
+        assert!(!x3f.same_image_as(&X3F::from_bytes(&copy).expect("valid X3F")));
+    }
+
+    #[test]
+    fn check_alignment_yields_misaligned_entries() {
+        let mut bytes = make_x3f(&[(b"PROP", &[0; 4]), (b"IMAG", &[0; 4]), (b"CAMF", &[0; 4])]);
+        let directory = bytes.len() - DirectoryPointerRef::LENGTH - 3 * 12 - 12;
+        bytes[directory + 24] += 2;
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let misaligned: Vec<(usize, u32)> = x3f.check_alignment().collect();
+        let offset = u32::try_from(HeaderRef::LENGTH + 6).expect("offset fits in u32");
+        assert_eq!(misaligned, [(1, offset)]);
+    }
+
+    #[test]
+    fn entry_at_offset_maps_offsets_to_sections() {
+        let bytes = make_x3f(&[(b"PROP", &[0; 6]), (b"IMAG", &[0; 4])]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let tag_at = |offset| {
+            x3f.entry_at_offset(offset)
+                .map(|entry| entry.entry_type_fourcc())
+        };
+
+        let start = HeaderRef::LENGTH;
+        assert_eq!(tag_at(start - 1), None);
+        assert_eq!(tag_at(start), Some(FourCc::PROP));
+        assert_eq!(tag_at(start + 5), Some(FourCc::PROP));
+        assert_eq!(tag_at(start + 6), None);
+        assert_eq!(tag_at(start + 8), Some(FourCc::IMAG));
+        assert_eq!(tag_at(x3f.directory_offset()), None);
+    }
+
+    #[test]
+    fn tagged_sections_pairs_entries_with_tags() {
+        let bytes = make_x3f(&[(b"PROP", &[]), (b"CAMF", &[])]);
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let tags: Vec<FourCc> = x3f.tagged_sections().map(|(tag, _)| tag).collect();
+        assert_eq!(tags, [FourCc::PROP, FourCc::CAMF]);
+    }
+
+    #[test]
+    fn unknown_section_tags_skips_known_tags() {
+        let bytes = make_x3f(&[
+            (b"PROP", &[]),
+            (b"XYZW", &[]),
+            (b"IMAG", &[]),
+            (b"XYZW", &[]),
+        ]);
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let tags: Vec<FourCc> = x3f.unknown_section_tags().collect();
+        assert_eq!(
+            tags,
+            [FourCc::from_ascii(b"XYZW"), FourCc::from_ascii(b"XYZW")]
+        );
+    }
 }
@@ -0,0 +1,37 @@
+/// Controls how strictly [`crate::X3F::from_bytes_with_options`] validates a file.
+///
+/// The default matches [`crate::X3F::from_bytes`]; [`ParseOptions::strict`] enables every check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseOptions {
+    /// Reject files with a directory entry whose data lies outside the file or starts
+    /// within the header or extended header.
+    pub validate_offsets: bool,
+    /// Reject files whose directory or known sections do not start with their identifier.
+    pub validate_identifiers: bool,
+    /// Reject files whose version calls for an extended header that is missing.
+    ///
+    /// When `false`, a missing extended header is treated as absent.
+    pub require_extended_header: bool,
+}
+
+impl ParseOptions {
+    /// Options enabling every check.
+    #[must_use]
+    pub const fn strict() -> Self {
+        Self {
+            validate_offsets: true,
+            validate_identifiers: true,
+            require_extended_header: true,
+        }
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            validate_offsets: false,
+            validate_identifiers: false,
+            require_extended_header: true,
+        }
+    }
+}
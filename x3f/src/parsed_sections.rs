@@ -0,0 +1,174 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+
+use crate::{Camf, DirectoryEntryRef, FourCc, Image, Prop, SectionData, X3F};
+
+/// Sections of a file grouped by type, as returned by [`X3F::parse_all`].
+#[derive(Debug, Default)]
+pub struct ParsedSections<'a> {
+    /// The first `PROP` section.
+    pub prop: Option<Prop<'a>>,
+    /// `IMAG` and `IMA2` sections in directory order.
+    pub images: Vec<Image<'a>>,
+    /// `CAMF` sections in directory order.
+    pub camf: Vec<Camf<'a>>,
+    /// Entries whose type is not `PROP`, `IMAG`, `IMA2` or `CAMF`.
+    pub unknown: Vec<(FourCc, DirectoryEntryRef<'a>)>,
+}
+
+impl<'a> X3F<'a> {
+    /// Parses every section and groups the results by type.
+    ///
+    /// Known sections that fail to parse are left out; see [`X3F::sections`] to inspect them.
+    #[must_use]
+    pub fn parse_all(&self) -> ParsedSections<'a> {
+        let mut parsed = ParsedSections::default();
+        for (entry, data) in self.sections() {
+            match data {
+                Some(SectionData::Prop(prop)) => {
+                    parsed.prop.get_or_insert(prop);
+                },
+                Some(SectionData::Image(image) | SectionData::Ima2(image)) => {
+                    parsed.images.push(image);
+                },
+                Some(SectionData::Camf(camf)) => parsed.camf.push(camf),
+                None => match entry.entry_type_fourcc() {
+                    FourCc::PROP | FourCc::IMAG | FourCc::IMA2 | FourCc::CAMF => {},
+                    tag => parsed.unknown.push((tag, entry)),
+                },
+            }
+        }
+        parsed
+    }
+
+    /// Decodes every entry of the first `PROP` section into owned strings.
+    ///
+    /// Unpaired surrogates decode to `U+FFFD`. If a name repeats, its first value is kept,
+    /// matching [`Prop::get`]. Returns an empty map if there is no `PROP` section.
+    #[must_use]
+    pub fn properties(&self) -> BTreeMap<String, String> {
+        let mut properties = BTreeMap::new();
+        if let Some(prop) = self.prop() {
+            for (name, value) in prop.entries() {
+                properties
+                    .entry(name.collect())
+                    .or_insert_with(|| value.collect());
+            }
+        }
+        properties
+    }
+
+    /// Groups directory entries by their type tag, in directory order within each group.
+    #[must_use]
+    pub fn entries_by_type(&self) -> BTreeMap<[u8; 4], Vec<DirectoryEntryRef<'a>>> {
+        let mut groups: BTreeMap<[u8; 4], Vec<DirectoryEntryRef<'a>>> = BTreeMap::new();
+        for (tag, entry) in self.tagged_sections() {
+            groups.entry(*tag.as_bytes()).or_default().push(entry);
+        }
+        groups
+    }
+
+    /// Iterates over directory entries from the largest declared data length to the
+    /// smallest, keeping directory order among entries of equal length.
+    ///
+    /// Collects the entries into a vector to sort them.
+    pub fn sections_by_size(&self) -> impl Iterator<Item = DirectoryEntryRef<'a>> {
+        let mut entries: Vec<DirectoryEntryRef<'a>> = self.directory().entries().collect();
+        entries.sort_by_key(|entry| Reverse(entry.data_length_u32()));
+        entries.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::test_util::{make_image_header, make_prop, make_x3f};
+
+    #[test]
+    fn parse_all_groups_sections_by_type() {
+        let first = make_prop(&[("ISO", "100")]);
+        let second = make_prop(&[("ISO", "200")]);
+        let image = make_image_header(2, 18);
+        let bytes = make_x3f(&[
+            (b"PROP", &first),
+            (b"IMAG", &image),
+            (b"XYZW", &[1, 2, 3, 4]),
+            (b"PROP", &second),
+            (b"IMA2", &image),
+            (b"CAMF", b"SECc"),
+        ]);
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let parsed = x3f.parse_all();
+        assert_eq!(parsed.prop.map(|prop| prop.as_bytes()), Some(&first[..]));
+        assert_eq!(parsed.images.len(), 2);
+        assert_eq!(parsed.camf.len(), 1);
+        assert_eq!(parsed.unknown.len(), 1);
+        assert_eq!(parsed.unknown[0].0, FourCc::from_ascii(b"XYZW"));
+        assert_eq!(parsed.unknown[0].1.data_length(), 4u32.to_le_bytes());
+    }
+
+    #[test]
+    fn properties_collects_sorted_owned_pairs() {
+        let prop = make_prop(&[("ISO", "100"), ("CAMMANUF", "SIGMA"), ("ISO", "200")]);
+        let bytes = make_x3f(&[(b"PROP", &prop)]);
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let properties: std::vec::Vec<(String, String)> = x3f.properties().into_iter().collect();
+        assert_eq!(
+            properties,
+            [
+                (String::from("CAMMANUF"), String::from("SIGMA")),
+                (String::from("ISO"), String::from("100")),
+            ]
+        );
+        assert!(
+            X3F::from_bytes(&make_x3f(&[]))
+                .expect("valid X3F")
+                .properties()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn entries_by_type_groups_in_tag_order() {
+        let bytes = make_x3f(&[(b"PROP", &[1; 4]), (b"IMAG", &[2; 4]), (b"PROP", &[3; 4])]);
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let groups = x3f.entries_by_type();
+        let tags: std::vec::Vec<&[u8; 4]> = groups.keys().collect();
+        assert_eq!(tags, [b"IMAG", b"PROP"]);
+
+        let props: std::vec::Vec<Option<&[u8]>> = groups[b"PROP"]
+            .iter()
+            .map(|entry| x3f.section_bytes(entry))
+            .collect();
+        assert_eq!(props, [Some(&[1u8; 4][..]), Some(&[3u8; 4][..])]);
+    }
+
+    #[test]
+    fn sections_by_size_orders_largest_first() {
+        let bytes = make_x3f(&[(b"PROP", &[1; 4]), (b"IMAG", &[2; 12]), (b"CAMF", &[3; 4])]);
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let tags: std::vec::Vec<FourCc> = x3f
+            .sections_by_size()
+            .map(|entry| entry.entry_type_fourcc())
+            .collect();
+        assert_eq!(tags, [FourCc::IMAG, FourCc::PROP, FourCc::CAMF]);
+    }
+
+    #[test]
+    fn parse_all_drops_malformed_known_sections() {
+        let bytes = make_x3f(&[(b"IMAG", &[0; 4])]);
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let parsed = x3f.parse_all();
+        assert!(parsed.images.is_empty());
+        assert!(parsed.unknown.is_empty());
+    }
+}
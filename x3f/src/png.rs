@@ -0,0 +1,250 @@
+use alloc::vec::Vec;
+use core::fmt;
+use std::io::{self, Write};
+
+use crate::{DataFormat, Image, X3F};
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+/// Largest payload of a stored (uncompressed) deflate block.
+const MAX_STORED_BLOCK: usize = 0xFFFF;
+/// PNG limits image dimensions to 2^31 - 1.
+const MAX_DIMENSION: u32 = 0x7FFF_FFFF;
+
+/// Error returned by [`X3F::write_preview_png`].
+#[derive(Debug)]
+pub enum PngError {
+    /// The file has no image section processed for preview.
+    NoPreview,
+    /// The preview is in a format this crate cannot decode yet.
+    UnsupportedFormat(DataFormat),
+    /// The preview's dimensions are zero, too large for PNG, or exceed its data.
+    InvalidImageData,
+    /// Writing to the output failed.
+    Io(io::Error),
+}
+
+impl fmt::Display for PngError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::NoPreview => f.write_str("file has no preview image"),
+            Self::UnsupportedFormat(format) => {
+                write!(
+                    f,
+                    "preview data format {} is not supported",
+                    format.as_u32()
+                )
+            },
+            Self::InvalidImageData => f.write_str("preview image data is invalid"),
+            Self::Io(err) => write!(f, "failed to write PNG: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for PngError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PngError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl X3F<'_> {
+    /// Encodes the first uncompressed RGB24 preview image as an 8-bit RGB PNG.
+    ///
+    /// The pixel data is stored without compression, so the output is roughly the size of
+    /// the decoded preview.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PngError::NoPreview`] if no image section is processed for preview,
+    /// [`PngError::UnsupportedFormat`] with the first preview's format if no preview is
+    /// uncompressed RGB24,
+    /// [`PngError::InvalidImageData`] if its dimensions are unusable, and
+    /// [`PngError::Io`] if writing fails.
+    pub fn write_preview_png<W: Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<(), PngError> {
+        let Some(preview) = self
+            .images()
+            .find(|image| image.is_preview() && image.is_uncompressed_rgb())
+        else {
+            let first_preview = self
+                .images()
+                .find(Image::is_preview)
+                .ok_or(PngError::NoPreview)?;
+            return Err(PngError::UnsupportedFormat(
+                first_preview.data_format_enum(),
+            ));
+        };
+
+        let width = preview.image_columns_u32();
+        let height = preview.image_rows_u32();
+        if !(1..=MAX_DIMENSION).contains(&width) || !(1..=MAX_DIMENSION).contains(&height) {
+            return Err(PngError::InvalidImageData);
+        }
+
+        let mut scanlines = Vec::new();
+        for row in preview.rgb24_rows().ok_or(PngError::InvalidImageData)? {
+            scanlines.push(0); // filter type: None
+            scanlines.extend_from_slice(row);
+        }
+
+        let mut header = [0u8; 13];
+        header[0..4].copy_from_slice(&width.to_be_bytes());
+        header[4..8].copy_from_slice(&height.to_be_bytes());
+        header[8] = 8; // bit depth
+        header[9] = 2; // color type: truecolor
+
+        writer.write_all(&SIGNATURE)?;
+        write_chunk(writer, *b"IHDR", &header)?;
+        write_chunk(writer, *b"IDAT", &zlib_stored(&scanlines))?;
+        write_chunk(writer, *b"IEND", &[])?;
+        Ok(())
+    }
+}
+
+fn write_chunk<W: Write>(
+    writer: &mut W,
+    kind: [u8; 4],
+    data: &[u8],
+) -> Result<(), PngError> {
+    let length = u32::try_from(data.len()).map_err(|_| PngError::InvalidImageData)?;
+    writer.write_all(&length.to_be_bytes())?;
+    writer.write_all(&kind)?;
+    writer.write_all(data)?;
+    writer.write_all(&crc32(&[&kind, data]).to_be_bytes())?;
+    Ok(())
+}
+
+/// Wraps `data` in a zlib stream of stored deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() + data.len() / MAX_STORED_BLOCK * 5 + 11);
+    out.extend_from_slice(&[0x78, 0x01]);
+
+    let mut blocks = data.chunks(MAX_STORED_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        let length = u16::try_from(block.len()).unwrap_or(u16::MAX);
+        out.push(u8::from(blocks.peek().is_none()));
+        out.extend_from_slice(&length.to_le_bytes());
+        out.extend_from_slice(&(!length).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(parts: &[&[u8]]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in parts.iter().flat_map(|part| part.iter()) {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MODULUS: u32 = 65521;
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % MODULUS;
+        b = (b + a) % MODULUS;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{make_image_header, make_x3f};
+
+    #[test]
+    fn checksums_match_reference_values() {
+        assert_eq!(crc32(&[b"IEND"]), 0xAE42_6082);
+        assert_eq!(crc32(&[b"123", b"456789"]), 0xCBF4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+
+    #[test]
+    fn zlib_stored_splits_long_input() {
+        let data = std::vec![7u8; MAX_STORED_BLOCK + 1];
+        let stream = zlib_stored(&data);
+        assert_eq!(stream.len(), 2 + 5 + MAX_STORED_BLOCK + 5 + 1 + 4);
+        assert_eq!(stream[2], 0);
+        assert_eq!(stream[2 + 5 + MAX_STORED_BLOCK], 1);
+    }
+
+    #[test]
+    fn write_preview_png_encodes_uncompressed_preview() {
+        let mut image = make_image_header(2, 3).to_vec();
+        image[16..20].copy_from_slice(&1u32.to_le_bytes());
+        image[20..24].copy_from_slice(&1u32.to_le_bytes());
+        image.extend_from_slice(&[0xFF, 0x00, 0x80, 0x00]);
+        let bytes = make_x3f(&[(b"IMAG", &image)]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+
+        let mut png = Vec::new();
+        x3f.write_preview_png(&mut png).expect("encodable preview");
+        assert_eq!(png[..8], SIGNATURE);
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], &[0, 0, 0, 1, 0, 0, 0, 1]);
+        let idat = 8 + 12 + 13;
+        assert_eq!(&png[idat + 4..idat + 8], b"IDAT");
+        assert_eq!(&png[idat + 8 + 7..idat + 8 + 11], &[0, 0xFF, 0x00, 0x80]);
+        assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+    }
+
+    #[test]
+    fn write_preview_png_skips_jpeg_preview() {
+        let mut image = make_image_header(2, 3).to_vec();
+        image[16..20].copy_from_slice(&1u32.to_le_bytes());
+        image[20..24].copy_from_slice(&1u32.to_le_bytes());
+        image.extend_from_slice(&[0xFF, 0x00, 0x80, 0x00]);
+        let bytes = make_x3f(&[(b"IMAG", &make_image_header(2, 18)), (b"IMAG", &image)]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+
+        let mut png = Vec::new();
+        x3f.write_preview_png(&mut png).expect("encodable preview");
+        assert_eq!(&png[16..24], &[0, 0, 0, 1, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn write_preview_png_rejects_unsupported_formats() {
+        let bytes = make_x3f(&[(b"IMAG", &make_image_header(2, 18))]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+
+        let err = x3f.write_preview_png(&mut Vec::new()).unwrap_err();
+        match err {
+            PngError::UnsupportedFormat(DataFormat::Jpeg) => {},
+            other => panic!("expected UnsupportedFormat, got {other:?}"),
+        }
+
+        let bytes = make_x3f(&[(b"IMAG", &make_image_header(1, 3))]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let err = x3f.write_preview_png(&mut Vec::new()).unwrap_err();
+        match err {
+            PngError::NoPreview => {},
+            other => panic!("expected NoPreview, got {other:?}"),
+        }
+    }
+}
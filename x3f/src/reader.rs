@@ -0,0 +1,101 @@
+use core::fmt;
+
+use crate::FourCc;
+use crate::byte_order::{read_u16_le, read_u32_le};
+use crate::debug_helper::TruncatedBytes;
+
+/// Bounds-checked cursor over section bytes.
+///
+/// Every read returns `None` without advancing when fewer bytes remain than requested.
+#[derive(Clone)]
+pub struct SectionReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl fmt::Debug for SectionReader<'_> {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("SectionReader")
+            .field("bytes", &TruncatedBytes(self.bytes))
+            .field("pos", &self.pos)
+            .finish()
+    }
+}
+
+impl<'a> SectionReader<'a> {
+    #[must_use]
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    #[must_use]
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.bytes[self.pos..]
+    }
+
+    pub fn read_u16_le(&mut self) -> Option<u16> {
+        let value = read_u16_le(self.bytes, self.pos)?;
+        self.pos += 2;
+        Some(value)
+    }
+
+    pub fn read_u32_le(&mut self) -> Option<u32> {
+        let value = read_u32_le(self.bytes, self.pos)?;
+        self.pos += 4;
+        Some(value)
+    }
+
+    pub fn read_bytes(
+        &mut self,
+        n: usize,
+    ) -> Option<&'a [u8]> {
+        let bytes = self.remaining().get(..n)?;
+        self.pos += n;
+        Some(bytes)
+    }
+
+    pub fn read_fourcc(&mut self) -> Option<FourCc> {
+        self.read_bytes(4).and_then(FourCc::from_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_advance_through_the_buffer() {
+        let bytes = *b"SECc\x01\x02\x03\x04\x05\x06\x07";
+        let mut reader = SectionReader::new(&bytes);
+
+        assert_eq!(reader.read_fourcc(), Some(FourCc::from_ascii(b"SECc")));
+        assert_eq!(reader.read_u32_le(), Some(0x0403_0201));
+        assert_eq!(reader.read_u16_le(), Some(0x0605));
+        assert_eq!(reader.position(), 10);
+        assert_eq!(reader.remaining(), &[0x07]);
+    }
+
+    #[test]
+    fn reads_past_end_return_none_without_advancing() {
+        let bytes = [1u8, 2, 3];
+        let mut reader = SectionReader::new(&bytes);
+
+        assert_eq!(reader.read_u32_le(), None);
+        assert_eq!(reader.read_fourcc(), None);
+        assert_eq!(reader.read_bytes(4), None);
+        assert_eq!(reader.position(), 0);
+
+        assert_eq!(reader.read_bytes(3), Some(&bytes[..]));
+        assert_eq!(reader.read_u16_le(), None);
+        assert_eq!(reader.read_bytes(0), Some(&[][..]));
+        assert_eq!(reader.position(), 3);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{Camf, DirectoryEntryRef, Image, Prop, SectionData, X3F};
+
+/// Decodes the data of directory entries with a given type tag.
+///
+/// Pass implementations to [`X3F::section_data_with`] to handle vendor or custom tags, or
+/// to override how a known tag is decoded. The built-in decoders are listed in
+/// [`BUILTIN_DECODERS`].
+pub trait SectionDecoder {
+    /// Returns the directory entry type this decoder handles.
+    fn tag(&self) -> [u8; 4];
+
+    /// Decodes a section's data, or returns `None` if it is malformed.
+    fn decode<'a>(
+        &self,
+        bytes: &'a [u8],
+    ) -> Option<SectionData<'a>>;
+}
+
+/// Decodes `PROP` sections as [`SectionData::Prop`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PropDecoder;
+
+/// Decodes `IMAG` sections as [`SectionData::Image`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ImagDecoder;
+
+/// Decodes `IMA2` sections as [`SectionData::Ima2`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Ima2Decoder;
+
+/// Decodes `CAMF` sections as [`SectionData::Camf`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CamfDecoder;
+
+/// The decoders [`X3F::section_data`] uses.
+pub const BUILTIN_DECODERS: &[&dyn SectionDecoder] =
+    &[&PropDecoder, &ImagDecoder, &Ima2Decoder, &CamfDecoder];
+
+impl SectionDecoder for PropDecoder {
+    fn tag(&self) -> [u8; 4] {
+        *b"PROP"
+    }
+
+    fn decode<'a>(
+        &self,
+        bytes: &'a [u8],
+    ) -> Option<SectionData<'a>> {
+        Prop::from_bytes(bytes).ok().map(SectionData::Prop)
+    }
+}
+
+impl SectionDecoder for ImagDecoder {
+    fn tag(&self) -> [u8; 4] {
+        *b"IMAG"
+    }
+
+    fn decode<'a>(
+        &self,
+        bytes: &'a [u8],
+    ) -> Option<SectionData<'a>> {
+        Image::from_bytes(bytes).ok().map(SectionData::Image)
+    }
+}
+
+impl SectionDecoder for Ima2Decoder {
+    fn tag(&self) -> [u8; 4] {
+        *b"IMA2"
+    }
+
+    fn decode<'a>(
+        &self,
+        bytes: &'a [u8],
+    ) -> Option<SectionData<'a>> {
+        Image::from_bytes(bytes).ok().map(SectionData::Ima2)
+    }
+}
+
+impl SectionDecoder for CamfDecoder {
+    fn tag(&self) -> [u8; 4] {
+        *b"CAMF"
+    }
+
+    fn decode<'a>(
+        &self,
+        bytes: &'a [u8],
+    ) -> Option<SectionData<'a>> {
+        Camf::from_bytes(bytes).ok().map(SectionData::Camf)
+    }
+}
+
+impl<'a> X3F<'a> {
+    /// Decodes the section described by `entry` with the first of `decoders` whose tag
+    /// matches the entry type, falling back to [`BUILTIN_DECODERS`].
+    ///
+    /// Returns `None` if the section lies outside the buffer, no decoder handles its type,
+    /// or the chosen decoder rejects it.
+    #[must_use]
+    pub fn section_data_with(
+        &self,
+        entry: &DirectoryEntryRef<'a>,
+        decoders: &[&dyn SectionDecoder],
+    ) -> Option<SectionData<'a>> {
+        let bytes = self.section_bytes(entry)?;
+        let tag = entry.entry_type();
+        decoders
+            .iter()
+            .chain(BUILTIN_DECODERS)
+            .find(|decoder| decoder.tag() == tag)?
+            .decode(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{make_image_header, make_x3f};
+
+    /// Decodes a vendor `THMB` section that stores a plain image section.
+    struct ThumbnailDecoder;
+
+    impl SectionDecoder for ThumbnailDecoder {
+        fn tag(&self) -> [u8; 4] {
+            *b"THMB"
+        }
+
+        fn decode<'a>(
+            &self,
+            bytes: &'a [u8],
+        ) -> Option<SectionData<'a>> {
+            Image::from_bytes(bytes).ok().map(SectionData::Ima2)
+        }
+    }
+
+    #[test]
+    fn section_data_with_uses_custom_decoders() {
+        let image = make_image_header(2, 18);
+        let bytes = make_x3f(&[(b"THMB", &image), (b"IMAG", &image)]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let mut entries = x3f.directory().entries();
+        let custom = entries.next().expect("custom entry");
+        let builtin = entries.next().expect("built-in entry");
+
+        assert!(x3f.section_data(&custom).is_none());
+        assert!(matches!(
+            x3f.section_data_with(&custom, &[&ThumbnailDecoder]),
+            Some(SectionData::Ima2(_))
+        ));
+        assert!(matches!(
+            x3f.section_data_with(&builtin, &[&ThumbnailDecoder]),
+            Some(SectionData::Image(_))
+        ));
+    }
+
+    #[test]
+    fn builtin_decoders_cover_known_tags() {
+        let tags: [[u8; 4]; 4] = core::array::from_fn(|index| BUILTIN_DECODERS[index].tag());
+        assert_eq!(tags, [*b"PROP", *b"IMAG", *b"IMA2", *b"CAMF"]);
+    }
+}
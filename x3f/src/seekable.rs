@@ -0,0 +1,272 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use std::io::{self, Read, Seek, SeekFrom};
+
+use crate::{DirectoryPointerRef, DirectoryRef, ExtendedHeaderRef, HeaderRef, X3F, X3FError};
+
+/// Error returned by [`X3F::from_seekable`] and [`SeekableX3F::read_section`].
+#[derive(Debug)]
+pub enum SeekableError {
+    /// Reading or seeking the underlying reader failed.
+    Io(io::Error),
+    /// The bytes read do not form a valid X3F structure.
+    Parse(X3FError),
+}
+
+impl fmt::Display for SeekableError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read X3F: {err}"),
+            Self::Parse(err) => write!(f, "failed to parse X3F: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for SeekableError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Parse(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for SeekableError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<X3FError> for SeekableError {
+    fn from(err: X3FError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+/// An X3F file read through a seekable reader, holding only its headers and directory.
+///
+/// Section data stays in the reader until requested with [`Self::read_section`], so
+/// indexing metadata of large files does not load the image data.
+pub struct SeekableX3F<R> {
+    reader: R,
+    header: [u8; HeaderRef::LENGTH],
+    extended_header: Option<[u8; ExtendedHeaderRef::LENGTH]>,
+    directory_offset: u64,
+    directory: Vec<u8>,
+    file_length: u64,
+}
+
+impl<R> fmt::Debug for SeekableX3F<R> {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("SeekableX3F")
+            .field("header", &self.header())
+            .field("extended_header", &self.extended_header())
+            .field("directory_offset", &self.directory_offset)
+            .field("directory", &self.directory())
+            .field("file_length", &self.file_length)
+            .finish_non_exhaustive()
+    }
+}
+
+impl X3F<'_> {
+    /// Reads the header, extended header and directory of a file from `reader`, leaving
+    /// section data to be read on demand.
+    ///
+    /// Applies the same checks as [`X3F::from_bytes`], apart from anything that needs the
+    /// section data itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SeekableError::Io`] if reading or seeking fails, and
+    /// [`SeekableError::Parse`] with the error [`X3F::from_bytes`] would return otherwise.
+    pub fn from_seekable<R: Read + Seek>(mut reader: R) -> Result<SeekableX3F<R>, SeekableError> {
+        let file_length = reader.seek(SeekFrom::End(0))?;
+        let header_length = HeaderRef::LENGTH as u64;
+        let pointer_length = DirectoryPointerRef::LENGTH as u64;
+        if file_length < header_length + pointer_length {
+            return Err(X3FError::TooShort.into());
+        }
+
+        let mut header = [0; HeaderRef::LENGTH];
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_exact(&mut header)?;
+        let header_ref = HeaderRef::from_array(&header);
+        if header_ref.file_type_identifier() != b"FOVb" {
+            return Err(X3FError::InvalidFileType {
+                found: header_ref.file_type_identifier_bytes(),
+            }
+            .into());
+        }
+
+        let extended_header = if header_ref.requires_extended_header() {
+            if file_length < header_length + ExtendedHeaderRef::LENGTH as u64 {
+                return Err(X3FError::TooShort.into());
+            }
+            let mut extended_header = [0; ExtendedHeaderRef::LENGTH];
+            reader.read_exact(&mut extended_header)?;
+            Some(extended_header)
+        } else {
+            None
+        };
+
+        let pointer_offset = file_length - pointer_length;
+        let mut pointer = [0; DirectoryPointerRef::LENGTH];
+        reader.seek(SeekFrom::Start(pointer_offset))?;
+        reader.read_exact(&mut pointer)?;
+        let directory_offset = u64::from(u32::from_le_bytes(pointer));
+        if directory_offset.saturating_add(12) > pointer_offset {
+            return Err(X3FError::OutOfBounds.into());
+        }
+
+        // Read only the entries the directory declares and the file can hold, so a bogus
+        // pointer cannot pull the rest of the file into memory.
+        let mut directory = vec![0; 12];
+        reader.seek(SeekFrom::Start(directory_offset))?;
+        reader.read_exact(&mut directory)?;
+        let declared = u64::from(DirectoryRef::from_bytes(&directory)?.entry_count_u32());
+        let entry_length = DirectoryRef::ENTRY_LENGTH as u64;
+        let available = (pointer_offset - directory_offset - 12) / entry_length;
+        let entries_length = usize::try_from(declared.min(available) * entry_length)
+            .map_err(|_| X3FError::OutOfBounds)?;
+        directory.resize(12 + entries_length, 0);
+        reader.read_exact(&mut directory[12..])?;
+
+        Ok(SeekableX3F {
+            reader,
+            header,
+            extended_header,
+            directory_offset,
+            directory,
+            file_length,
+        })
+    }
+}
+
+impl<R> SeekableX3F<R> {
+    #[must_use]
+    pub fn header(&self) -> HeaderRef<'_> {
+        HeaderRef::from_array(&self.header)
+    }
+
+    #[must_use]
+    pub fn extended_header(&self) -> Option<ExtendedHeaderRef<'_>> {
+        self.extended_header
+            .as_ref()
+            .map(ExtendedHeaderRef::from_array)
+    }
+
+    /// Returns the directory header and the entries it declares, limited to those that
+    /// fit before the directory pointer. Any bytes after them are not read.
+    #[must_use]
+    pub fn directory(&self) -> DirectoryRef<'_> {
+        DirectoryRef::from_bytes(&self.directory).unwrap_or_else(|_| {
+            unreachable!("directory is at least 12 bytes long");
+        })
+    }
+
+    /// Returns the offset of the directory section from the start of the file.
+    #[must_use]
+    pub fn directory_offset(&self) -> u64 {
+        self.directory_offset
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read + Seek> SeekableX3F<R> {
+    /// Reads the data of the `entry_index`-th directory entry from the reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SeekableError::Parse`] with [`X3FError::OutOfBounds`] if there is no such
+    /// entry or its data lies outside the file, and [`SeekableError::Io`] if reading fails.
+    pub fn read_section(
+        &mut self,
+        entry_index: usize,
+    ) -> Result<Vec<u8>, SeekableError> {
+        let (offset, length) = self
+            .directory()
+            .entry(entry_index)
+            .map(|entry| {
+                (
+                    u64::from(entry.data_offset_u32()),
+                    u64::from(entry.data_length_u32()),
+                )
+            })
+            .ok_or(X3FError::OutOfBounds)?;
+        if offset.saturating_add(length) > self.file_length {
+            return Err(X3FError::OutOfBounds.into());
+        }
+
+        let mut data = vec![0; usize::try_from(length).map_err(|_| X3FError::OutOfBounds)?];
+        self.reader.seek(SeekFrom::Start(offset))?;
+        self.reader.read_exact(&mut data)?;
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{make_prop, make_x3f};
+    use std::io::Cursor;
+
+    #[test]
+    fn from_seekable_matches_from_bytes() {
+        let prop = make_prop(&[("ISO", "100")]);
+        let bytes = make_x3f(&[(b"CAMF", b"SECcabcd"), (b"PROP", &prop)]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+
+        let mut seekable = X3F::from_seekable(Cursor::new(&bytes)).expect("valid X3F");
+        assert_eq!(seekable.header().as_bytes(), x3f.header().as_bytes());
+        assert!(seekable.extended_header().is_none());
+        assert_eq!(seekable.directory().as_bytes(), x3f.directory_bytes());
+        assert_eq!(
+            usize::try_from(seekable.directory_offset()).ok(),
+            Some(x3f.directory_offset())
+        );
+        assert_eq!(
+            seekable.read_section(1).expect("section in bounds"),
+            prop.as_slice()
+        );
+        match seekable.read_section(2).unwrap_err() {
+            SeekableError::Parse(X3FError::OutOfBounds) => {},
+            other => panic!("expected OutOfBounds, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn from_seekable_reads_only_declared_entries() {
+        let mut bytes = make_x3f(&[(b"XYZW", &[0; 4096])]);
+        let pointer_offset = bytes.len() - DirectoryPointerRef::LENGTH;
+        bytes[pointer_offset..].copy_from_slice(&40u32.to_le_bytes());
+
+        let seekable = X3F::from_seekable(Cursor::new(&bytes)).expect("valid X3F");
+        assert_eq!(seekable.directory_offset(), 40);
+        assert_eq!(seekable.directory().as_bytes().len(), 12);
+        assert_eq!(seekable.directory().entries().count(), 0);
+    }
+
+    #[test]
+    fn from_seekable_rejects_invalid_file_type() {
+        let mut bytes = make_x3f(&[]);
+        bytes[0..4].copy_from_slice(b"JUNK");
+
+        match X3F::from_seekable(Cursor::new(&bytes)).unwrap_err() {
+            SeekableError::Parse(X3FError::InvalidFileType { found }) => {
+                assert_eq!(&found, b"JUNK");
+            },
+            other => panic!("expected InvalidFileType, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::X3F;
+
+/// Sensor family of a camera, which determines the raw data layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SensorGeneration {
+    /// Three full-resolution Foveon layers (SD1 and the Merrill series).
+    Merrill,
+    /// Foveon Quattro, whose lower layers have a quarter of the top layer's resolution.
+    Quattro,
+    /// Conventional Bayer sensor (fp series).
+    BayerFp,
+    /// A model not in the table, including pre-Merrill Foveon cameras.
+    Unknown,
+}
+
+/// Camera models as written in the `CAMMODEL` property.
+const MODELS: &[(&str, SensorGeneration)] = &[
+    ("SIGMA SD1", SensorGeneration::Merrill),
+    ("SIGMA SD1 Merrill", SensorGeneration::Merrill),
+    ("SIGMA DP1 Merrill", SensorGeneration::Merrill),
+    ("SIGMA DP2 Merrill", SensorGeneration::Merrill),
+    ("SIGMA DP3 Merrill", SensorGeneration::Merrill),
+    ("SIGMA dp0 Quattro", SensorGeneration::Quattro),
+    ("SIGMA dp1 Quattro", SensorGeneration::Quattro),
+    ("SIGMA dp2 Quattro", SensorGeneration::Quattro),
+    ("SIGMA dp3 Quattro", SensorGeneration::Quattro),
+    ("SIGMA sd Quattro", SensorGeneration::Quattro),
+    ("SIGMA sd Quattro H", SensorGeneration::Quattro),
+    ("SIGMA fp", SensorGeneration::BayerFp),
+    ("SIGMA fp L", SensorGeneration::BayerFp),
+];
+
+impl X3F<'_> {
+    /// Infers the sensor generation from the `CAMMODEL` property.
+    ///
+    /// Returns `None` if the file has no `CAMMODEL` property, and
+    /// [`SensorGeneration::Unknown`] if the model is not in the crate's table.
+    #[must_use]
+    pub fn sensor_generation(&self) -> Option<SensorGeneration> {
+        let model = self.prop()?.get("CAMMODEL")?;
+        let generation = MODELS
+            .iter()
+            .find(|(name, _)| model.clone().eq(name.chars()))
+            .map_or(SensorGeneration::Unknown, |&(_, generation)| generation);
+        Some(generation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{make_prop, make_x3f};
+
+    fn generation_of(model: &str) -> Option<SensorGeneration> {
+        let bytes = make_x3f(&[(b"PROP", &make_prop(&[("CAMMODEL", model)]))]);
+        X3F::from_bytes(&bytes)
+            .expect("valid X3F")
+            .sensor_generation()
+    }
+
+    #[test]
+    fn sensor_generation_looks_up_model() {
+        assert_eq!(
+            generation_of("SIGMA DP2 Merrill"),
+            Some(SensorGeneration::Merrill)
+        );
+        assert_eq!(
+            generation_of("SIGMA sd Quattro H"),
+            Some(SensorGeneration::Quattro)
+        );
+        assert_eq!(generation_of("SIGMA fp"), Some(SensorGeneration::BayerFp));
+        assert_eq!(generation_of("SIGMA SD14"), Some(SensorGeneration::Unknown));
+    }
+
+    #[test]
+    fn sensor_generation_requires_model_property() {
+        let bytes = make_x3f(&[(b"PROP", &make_prop(&[("ISO", "100")]))]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        assert_eq!(x3f.sensor_generation(), None);
+    }
+}
@@ -0,0 +1,91 @@
+use alloc::collections::BTreeMap;
+
+use crate::{FourCc, X3F};
+
+/// Section totals for one type tag, as collected by [`aggregate_section_stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SectionTypeStats {
+    /// Number of sections with this tag across all files.
+    pub count: usize,
+    /// Number of files with at least one section of this tag.
+    pub files: usize,
+    /// Sum of the declared data lengths of these sections.
+    pub total_bytes: u64,
+}
+
+/// Section totals across a batch of files, as returned by [`aggregate_section_stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SectionStats {
+    /// Number of files tallied.
+    pub files: usize,
+    /// Sum of [`X3F::total_section_bytes`] over all files.
+    pub total_bytes: u64,
+    /// Totals keyed by section type tag.
+    pub by_type: BTreeMap<FourCc, SectionTypeStats>,
+}
+
+/// Tallies section counts and declared byte totals per type tag across `files`.
+///
+/// Lengths are taken from the directory entries, as in [`X3F::total_section_bytes`], so
+/// sections lying outside their buffer are still counted.
+#[must_use]
+pub fn aggregate_section_stats(files: &[X3F<'_>]) -> SectionStats {
+    let mut stats = SectionStats {
+        files: files.len(),
+        ..SectionStats::default()
+    };
+    for x3f in files {
+        stats.total_bytes += x3f.total_section_bytes();
+        let mut seen: BTreeMap<FourCc, SectionTypeStats> = BTreeMap::new();
+        for (tag, entry) in x3f.tagged_sections() {
+            let totals = seen.entry(tag).or_default();
+            totals.count += 1;
+            totals.total_bytes += u64::from(entry.data_length_u32());
+        }
+        for (tag, file_totals) in seen {
+            let totals = stats.by_type.entry(tag).or_default();
+            totals.count += file_totals.count;
+            totals.files += 1;
+            totals.total_bytes += file_totals.total_bytes;
+        }
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::make_x3f;
+
+    #[test]
+    fn aggregate_section_stats_tallies_across_files() {
+        let first = make_x3f(&[(b"CAMF", &[0; 8]), (b"IMAG", &[0; 12]), (b"IMAG", &[0; 4])]);
+        let second = make_x3f(&[(b"IMAG", &[0; 20])]);
+        let files = [
+            X3F::from_bytes(&first).expect("valid X3F"),
+            X3F::from_bytes(&second).expect("valid X3F"),
+        ];
+
+        let stats = aggregate_section_stats(&files);
+        assert_eq!(stats.files, 2);
+        assert_eq!(stats.total_bytes, 44);
+        assert_eq!(
+            stats.by_type[&FourCc::CAMF],
+            SectionTypeStats {
+                count: 1,
+                files: 1,
+                total_bytes: 8,
+            }
+        );
+        assert_eq!(
+            stats.by_type[&FourCc::IMAG],
+            SectionTypeStats {
+                count: 3,
+                files: 2,
+                total_bytes: 36,
+            }
+        );
+        assert_eq!(stats.by_type.len(), 2);
+        assert_eq!(aggregate_section_stats(&[]), SectionStats::default());
+    }
+}
@@ -0,0 +1,191 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+use crate::{FourCc, Rotation, X3F, X3FError};
+
+/// Owned overview of an X3F file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Summary {
+    /// `CAMMANUF` property.
+    pub make: Option<String>,
+    /// `CAMMODEL` property.
+    pub model: Option<String>,
+    pub columns: u32,
+    pub rows: u32,
+    pub rotation: Rotation,
+    /// `TIME` property, in seconds since the Unix epoch.
+    pub capture_time: Option<u64>,
+    /// Directory entry types in directory order.
+    pub section_tags: Vec<FourCc>,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        if let Some(make) = &self.make {
+            writeln!(f, "Make: {make}")?;
+        }
+        if let Some(model) = &self.model {
+            writeln!(f, "Model: {model}")?;
+        }
+        writeln!(f, "Dimensions: {}x{}", self.columns, self.rows)?;
+        writeln!(f, "Rotation: {}", self.rotation.as_u32())?;
+        if let Some(capture_time) = self.capture_time {
+            writeln!(f, "Capture time: {capture_time}")?;
+        }
+        write!(f, "Sections:")?;
+        for tag in &self.section_tags {
+            write!(f, " {tag}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses `bytes` and collects the header, `PROP` and directory into a [`Summary`].
+///
+/// # Errors
+///
+/// Returns any error from [`X3F::from_bytes`].
+pub fn summarize(bytes: &[u8]) -> Result<Summary, X3FError> {
+    let x3f = X3F::from_bytes(bytes)?;
+    let header = x3f.header();
+    let prop = x3f.prop();
+    let mut section_tags = Vec::with_capacity(x3f.directory().bounded_entry_count());
+    section_tags.extend(x3f.tagged_sections().map(|(tag, _)| tag));
+    let property = |name: &str| prop.as_ref()?.get(name).map(Iterator::collect::<String>);
+
+    Ok(Summary {
+        make: property("CAMMANUF"),
+        model: property("CAMMODEL"),
+        columns: header.image_columns_u32(),
+        rows: header.image_rows_u32(),
+        rotation: header.rotation_enum(),
+        capture_time: property("TIME").and_then(|time| time.trim().parse().ok()),
+        section_tags,
+    })
+}
+
+impl X3F<'_> {
+    /// Formats the main capture settings as `Key: Value` lines, each ending in `\n`.
+    ///
+    /// Lines appear in this order, and any whose value is missing is omitted:
+    ///
+    /// | Key | Source |
+    /// | --- | --- |
+    /// | `Make` | `CAMMANUF` property |
+    /// | `Model` | `CAMMODEL` property |
+    /// | `Image Width` | Header image columns |
+    /// | `Image Height` | Header image rows |
+    /// | `ISO` | `ISO` property |
+    /// | `Exposure Time` | `SH_DESC` property |
+    /// | `White Balance` | `WB` property, or the extended header label |
+    #[must_use]
+    pub fn to_exif_text(&self) -> String {
+        let prop = self.prop();
+        let property = |name: &str| prop.as_ref()?.get(name).map(Iterator::collect::<String>);
+        let white_balance = property("WB").or_else(|| {
+            let label = self.extended_header()?.white_balance_label();
+            (!label.is_empty()).then(|| String::from_utf8_lossy(label).into_owned())
+        });
+
+        let fields = [
+            ("Make", property("CAMMANUF")),
+            ("Model", property("CAMMODEL")),
+            (
+                "Image Width",
+                Some(self.header().image_columns_u32().to_string()),
+            ),
+            (
+                "Image Height",
+                Some(self.header().image_rows_u32().to_string()),
+            ),
+            ("ISO", property("ISO")),
+            ("Exposure Time", property("SH_DESC")),
+            ("White Balance", white_balance),
+        ];
+
+        let mut text = String::new();
+        for (key, value) in fields {
+            if let Some(value) = value {
+                let _ = writeln!(text, "{key}: {value}");
+            }
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::test_util::{make_prop, make_x3f, make_x3f_with_version};
+    use std::string::ToString;
+
+    #[test]
+    fn summarize_collects_header_properties_and_tags() {
+        let prop = make_prop(&[
+            ("CAMMANUF", "SIGMA"),
+            ("CAMMODEL", "SIGMA DP2 Merrill"),
+            ("TIME", "1350000000"),
+        ]);
+        let bytes = make_x3f(&[(b"PROP", &prop), (b"CAMF", b"SECc")]);
+
+        let summary = summarize(&bytes).expect("valid X3F");
+        assert_eq!(summary.make.as_deref(), Some("SIGMA"));
+        assert_eq!(summary.model.as_deref(), Some("SIGMA DP2 Merrill"));
+        assert_eq!(summary.rotation, Rotation::Deg0);
+        assert_eq!(summary.capture_time, Some(1_350_000_000));
+        assert_eq!(summary.section_tags, [FourCc::PROP, FourCc::CAMF]);
+        assert_eq!(
+            summary.to_string(),
+            "Make: SIGMA\nModel: SIGMA DP2 Merrill\nDimensions: 0x0\nRotation: 0\n\
+             Capture time: 1350000000\nSections: PROP CAMF"
+        );
+    }
+
+    #[test]
+    fn to_exif_text_lists_present_fields() {
+        let prop = make_prop(&[
+            ("CAMMODEL", "SIGMA dp2 Quattro"),
+            ("ISO", "200"),
+            ("SH_DESC", "1/125"),
+            ("WB", "Auto"),
+        ]);
+        let mut bytes = make_x3f(&[(b"PROP", &prop)]);
+        bytes[28..32].copy_from_slice(&5424u32.to_le_bytes());
+        bytes[32..36].copy_from_slice(&3616u32.to_le_bytes());
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        assert_eq!(
+            x3f.to_exif_text(),
+            "Model: SIGMA dp2 Quattro\nImage Width: 5424\nImage Height: 3616\nISO: 200\n\
+             Exposure Time: 1/125\nWhite Balance: Auto\n"
+        );
+    }
+
+    #[test]
+    fn to_exif_text_falls_back_to_extended_header_label() {
+        let mut bytes = make_x3f_with_version(0x0002_0002, &[]);
+        bytes[40..47].copy_from_slice(b"Sunlit\0");
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        assert_eq!(
+            x3f.to_exif_text(),
+            "Image Width: 0\nImage Height: 0\nWhite Balance: Sunlit\n"
+        );
+    }
+
+    #[test]
+    fn summarize_omits_missing_properties() {
+        let bytes = make_x3f(&[(b"IMAG", &[0; 28])]);
+
+        let summary = summarize(&bytes).expect("valid X3F");
+        assert_eq!(summary.make, None);
+        assert_eq!(summary.capture_time, None);
+        assert_eq!(summary.section_tags, [FourCc::IMAG]);
+    }
+}
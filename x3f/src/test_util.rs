@@ -0,0 +1,93 @@
+//! Fixture builders shared by unit tests.
+
+extern crate std;
+
+use std::vec::Vec;
+
+use crate::{ExtendedHeaderRef, HeaderRef, Image};
+
+pub(crate) fn le_u32(value: usize) -> [u8; 4] {
+    u32::try_from(value)
+        .expect("value fits in u32")
+        .to_le_bytes()
+}
+
+pub(crate) fn make_header(file_format_version: [u8; 4]) -> [u8; HeaderRef::LENGTH] {
+    let mut header = [0u8; HeaderRef::LENGTH];
+    header[0..4].copy_from_slice(b"FOVb");
+    header[4..8].copy_from_slice(&file_format_version);
+    header
+}
+
+pub(crate) fn make_prop(pairs: &[(&str, &str)]) -> Vec<u8> {
+    let mut table = Vec::new();
+    let mut chars: Vec<u16> = Vec::new();
+    for (name, value) in pairs {
+        table.extend_from_slice(&le_u32(chars.len()));
+        chars.extend(name.encode_utf16());
+        chars.push(0);
+        table.extend_from_slice(&le_u32(chars.len()));
+        chars.extend(value.encode_utf16());
+        chars.push(0);
+    }
+
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"SECp");
+    bytes.extend_from_slice(&0x0002_0000u32.to_le_bytes());
+    bytes.extend_from_slice(&le_u32(pairs.len()));
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&le_u32(chars.len()));
+    bytes.extend_from_slice(&table);
+    for unit in chars {
+        bytes.extend_from_slice(&unit.to_le_bytes());
+    }
+    bytes
+}
+
+pub(crate) fn make_image_header(
+    type_of_image_data: u32,
+    data_format: u32,
+) -> [u8; Image::LENGTH] {
+    let mut bytes = [0u8; Image::LENGTH];
+    bytes[0..4].copy_from_slice(b"SECi");
+    bytes[8..12].copy_from_slice(&type_of_image_data.to_le_bytes());
+    bytes[12..16].copy_from_slice(&data_format.to_le_bytes());
+    bytes
+}
+
+/// Lays out a version 0 file with the given sections, a directory and its pointer.
+pub(crate) fn make_x3f(sections: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+    make_x3f_with_version(0, sections)
+}
+
+/// Like [`make_x3f`], adding a zeroed extended header when `version` requires one.
+pub(crate) fn make_x3f_with_version(
+    version: u32,
+    sections: &[(&[u8; 4], &[u8])],
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&make_header(version.to_le_bytes()));
+    if version > HeaderRef::EXTENDED_HEADER_VERSION_THRESHOLD {
+        bytes.extend_from_slice(&[0u8; ExtendedHeaderRef::LENGTH]);
+    }
+
+    let mut entries = Vec::new();
+    for (tag, data) in sections {
+        entries.push((le_u32(bytes.len()), le_u32(data.len()), **tag));
+        bytes.extend_from_slice(data);
+        bytes.resize(bytes.len().next_multiple_of(4), 0);
+    }
+
+    let directory_offset = le_u32(bytes.len());
+    bytes.extend_from_slice(b"SECd");
+    bytes.extend_from_slice(&0x0002_0000u32.to_le_bytes());
+    bytes.extend_from_slice(&le_u32(entries.len()));
+    for (offset, length, tag) in entries {
+        bytes.extend_from_slice(&offset);
+        bytes.extend_from_slice(&length);
+        bytes.extend_from_slice(&tag);
+    }
+    bytes.extend_from_slice(&directory_offset);
+    bytes
+}
@@ -0,0 +1,110 @@
+use core::ops::Range;
+
+use crate::{DirectoryPointerRef, HeaderRef, X3F};
+
+/// Contiguous span of a file, by offset and length in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ByteRegion {
+    pub offset: usize,
+    pub length: usize,
+}
+
+impl ByteRegion {
+    /// Returns the offset just past the region, saturating at `usize::MAX`.
+    #[must_use]
+    pub const fn end(self) -> usize {
+        self.offset.saturating_add(self.length)
+    }
+
+    #[must_use]
+    pub const fn range(self) -> Range<usize> {
+        self.offset..self.end()
+    }
+}
+
+/// Where each region of a file was found while parsing, as returned by [`X3F::parse_trace`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseTrace {
+    pub header: ByteRegion,
+    /// `None` if the file has no extended header.
+    pub extended_header: Option<ByteRegion>,
+    /// Between the end of the (extended) header and the directory; see [`X3F::data_region`].
+    pub data_region: ByteRegion,
+    /// From the directory offset up to the directory pointer.
+    pub directory: ByteRegion,
+    pub directory_pointer: ByteRegion,
+}
+
+impl X3F<'_> {
+    /// Returns the byte regions this file was parsed from.
+    #[must_use]
+    pub fn parse_trace(&self) -> ParseTrace {
+        let data_start = self.data_start_offset();
+        ParseTrace {
+            header: ByteRegion {
+                offset: 0,
+                length: HeaderRef::LENGTH,
+            },
+            extended_header: self.extended_header_range().map(|range| ByteRegion {
+                offset: range.start,
+                length: range.len(),
+            }),
+            data_region: ByteRegion {
+                offset: data_start,
+                length: self.directory_offset.saturating_sub(data_start),
+            },
+            directory: ByteRegion {
+                offset: self.directory_offset,
+                length: self.directory.as_bytes().len(),
+            },
+            directory_pointer: ByteRegion {
+                offset: self.directory_pointer_offset,
+                length: DirectoryPointerRef::LENGTH,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ExtendedHeaderRef;
+    use crate::test_util::{make_x3f, make_x3f_with_version};
+
+    #[test]
+    fn parse_trace_records_contiguous_regions() {
+        let bytes = make_x3f_with_version(0x0002_0001, &[(b"XYZW", &[0; 8])]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let trace = x3f.parse_trace();
+
+        let extended = trace.extended_header.expect("extended header");
+        assert_eq!(
+            extended.range(),
+            HeaderRef::LENGTH..HeaderRef::LENGTH + ExtendedHeaderRef::LENGTH
+        );
+        assert_eq!(trace.data_region.offset, extended.end());
+        assert_eq!(trace.data_region.length, 8);
+        assert_eq!(trace.directory.offset, trace.data_region.end());
+        assert_eq!(trace.directory.length, 24);
+        assert_eq!(trace.directory_pointer.offset, trace.directory.end());
+        assert_eq!(trace.directory_pointer.end(), bytes.len());
+    }
+
+    #[test]
+    fn byte_region_end_saturates() {
+        let region = ByteRegion {
+            offset: usize::MAX,
+            length: 1,
+        };
+        assert_eq!(region.end(), usize::MAX);
+        assert!(region.range().is_empty());
+    }
+
+    #[test]
+    fn parse_trace_omits_missing_extended_header() {
+        let bytes = make_x3f(&[]);
+        let trace = X3F::from_bytes(&bytes).expect("valid X3F").parse_trace();
+        assert_eq!(trace.extended_header, None);
+        assert_eq!(trace.data_region.offset, HeaderRef::LENGTH);
+    }
+}
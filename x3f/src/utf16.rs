@@ -0,0 +1,110 @@
+use core::fmt;
+
+use crate::byte_order::read_u16_le;
+use crate::debug_helper::TruncatedBytes;
+
+/// Lazily decoded UTF-16LE string.
+///
+/// Yields `char`s, substituting `U+FFFD` for unpaired surrogates.
+#[derive(Clone)]
+pub struct Utf16Chars<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl fmt::Debug for Utf16Chars<'_> {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("Utf16Chars")
+            .field("bytes", &TruncatedBytes(self.bytes))
+            .field("pos", &self.pos)
+            .finish()
+    }
+}
+
+impl<'a> Utf16Chars<'a> {
+    /// Creates a decoder over UTF-16LE code units. A trailing odd byte is ignored.
+    #[must_use]
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn next_unit(&mut self) -> Option<u16> {
+        let unit = self.peek_unit()?;
+        self.pos += 2;
+        Some(unit)
+    }
+
+    fn peek_unit(&self) -> Option<u16> {
+        read_u16_le(self.bytes, self.pos)
+    }
+}
+
+impl Iterator for Utf16Chars<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let unit = self.next_unit()?;
+        match unit {
+            0xD800..=0xDBFF => match self.peek_unit() {
+                Some(low @ 0xDC00..=0xDFFF) => {
+                    self.pos += 2;
+                    let scalar =
+                        0x1_0000 + ((u32::from(unit) - 0xD800) << 10) + (u32::from(low) - 0xDC00);
+                    Some(char::from_u32(scalar).unwrap_or(char::REPLACEMENT_CHARACTER))
+                },
+                _ => Some(char::REPLACEMENT_CHARACTER),
+            },
+            0xDC00..=0xDFFF => Some(char::REPLACEMENT_CHARACTER),
+            _ => Some(char::from_u32(u32::from(unit)).unwrap_or(char::REPLACEMENT_CHARACTER)),
+        }
+    }
+}
+
+/// Returns the UTF-16LE string starting at `char_offset` code units into `data`,
+/// excluding its NUL terminator. Unterminated strings run to the end of `data`.
+pub(crate) fn nul_terminated_at(
+    data: &[u8],
+    char_offset: u32,
+) -> Option<&[u8]> {
+    let start = usize::try_from(char_offset).ok()?.checked_mul(2)?;
+    let rest = data.get(start..)?;
+    let units = rest
+        .chunks_exact(2)
+        .position(|unit| unit == [0, 0])
+        .unwrap_or(rest.len() / 2);
+    Some(&rest[..units * 2])
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::string::String;
+
+    #[test]
+    fn utf16_chars_decodes_surrogate_pairs() {
+        // "a", U+1F600, "b"
+        let bytes = [0x61, 0x00, 0x3D, 0xD8, 0x00, 0xDE, 0x62, 0x00];
+        let decoded: String = Utf16Chars::new(&bytes).collect();
+        assert_eq!(decoded, "a\u{1F600}b");
+    }
+
+    #[test]
+    fn utf16_chars_replaces_unpaired_surrogates() {
+        let bytes = [0x3D, 0xD8, 0x61, 0x00, 0x00, 0xDC];
+        let decoded: String = Utf16Chars::new(&bytes).collect();
+        assert_eq!(decoded, "\u{FFFD}a\u{FFFD}");
+    }
+
+    #[test]
+    fn nul_terminated_at_stops_at_terminator() {
+        let data = [0x41, 0x00, 0x00, 0x00, 0x42, 0x00, 0x43, 0x00];
+        assert_eq!(nul_terminated_at(&data, 0), Some(&data[0..2]));
+        assert_eq!(nul_terminated_at(&data, 2), Some(&data[4..8]));
+        assert_eq!(nul_terminated_at(&data, 5), None);
+    }
+}
@@ -0,0 +1,106 @@
+use core::fmt;
+
+/// Format version, stored as a `u32` with the major version in the upper 16 bits and the
+/// minor version in the lower 16 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Version {
+    major: u16,
+    minor: u16,
+}
+
+impl Version {
+    #[must_use]
+    pub const fn new(
+        major: u16,
+        minor: u16,
+    ) -> Self {
+        Self { major, minor }
+    }
+
+    #[must_use]
+    pub const fn from_raw(raw: u32) -> Self {
+        Self {
+            major: (raw >> 16) as u16,
+            minor: (raw & 0xFFFF) as u16,
+        }
+    }
+
+    /// Parses `"major.minor"`, ignoring trailing NULs (e.g. `"2.0\0"`).
+    #[must_use]
+    pub fn from_ascii(s: &str) -> Option<Self> {
+        let (major, minor) = s.trim_end_matches('\0').split_once('.')?;
+        Some(Self::new(major.parse().ok()?, minor.parse().ok()?))
+    }
+
+    #[must_use]
+    pub const fn raw(self) -> u32 {
+        ((self.major as u32) << 16) | self.minor as u32
+    }
+
+    #[must_use]
+    pub const fn major(self) -> u16 {
+        self.major
+    }
+
+    #[must_use]
+    pub const fn minor(self) -> u16 {
+        self.minor
+    }
+
+    /// Returns the `"major.minor"` form, as written by [`fmt::Display`].
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn display_string(self) -> alloc::string::String {
+        alloc::format!("{self}")
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_ascii_round_trips_raw() {
+        let version = Version::from_ascii("2.1").expect("valid version");
+        assert_eq!(version, Version::new(2, 1));
+        assert_eq!(version.raw(), 0x0002_0001);
+        assert_eq!(Version::from_raw(version.raw()), version);
+    }
+
+    #[test]
+    fn from_ascii_trims_trailing_nuls() {
+        assert_eq!(Version::from_ascii("2.0\0"), Some(Version::new(2, 0)));
+        assert_eq!(Version::from_ascii("2\0"), None);
+        assert_eq!(Version::from_ascii("2.x"), None);
+        assert_eq!(Version::from_ascii("70000.0"), None);
+    }
+
+    #[test]
+    fn display_writes_major_dot_minor() {
+        extern crate std;
+
+        assert_eq!(std::format!("{}", Version::new(2, 1)), "2.1");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn display_string_matches_display() {
+        assert_eq!(Version::new(2, 10).display_string(), "2.10");
+    }
+
+    #[test]
+    fn versions_order_by_major_then_minor() {
+        assert!(Version::new(2, 1) > Version::new(2, 0));
+        assert!(Version::new(3, 0) > Version::new(2, 2));
+        assert!(Version::from_ascii("2.2") >= Version::from_ascii("2.1"));
+    }
+}
@@ -0,0 +1,86 @@
+use crate::{ExtendedDataType, ExtendedValue, X3F};
+
+/// White balance settings recorded in the extended header, as returned by
+/// [`X3F::white_balance_info`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WhiteBalanceInfo<'a> {
+    /// White balance label, up to its first NUL byte. Usually ASCII, such as `b"Auto"`.
+    pub label: &'a [u8],
+    /// Value of the first `RedAdjust` extended data slot.
+    pub red_adjust: Option<f32>,
+    /// Value of the first `GreenAdjust` extended data slot.
+    pub green_adjust: Option<f32>,
+    /// Value of the first `BlueAdjust` extended data slot.
+    pub blue_adjust: Option<f32>,
+}
+
+impl<'a> WhiteBalanceInfo<'a> {
+    /// Returns the label as a string, or `None` if it is not valid UTF-8.
+    #[must_use]
+    pub fn label_str(&self) -> Option<&'a str> {
+        core::str::from_utf8(self.label).ok()
+    }
+}
+
+impl<'a> X3F<'a> {
+    /// Returns the white balance label together with the red, green and blue adjustments
+    /// from the extended data slots, or `None` if the file has no extended header.
+    ///
+    /// The spec defines no color temperature slot, so only the `RedAdjust`,
+    /// `GreenAdjust` and `BlueAdjust` types are consulted. Each adjustment is `None` when
+    /// no slot has its type, leaving just the label.
+    #[must_use]
+    pub fn white_balance_info(&self) -> Option<WhiteBalanceInfo<'a>> {
+        let extended = self.extended_header.as_ref()?;
+        let label = extended.white_balance_label();
+        let adjust = |data_type| {
+            (0..32)
+                .find(|&index| extended.extended_data_type_enum(index) == Some(data_type))
+                .and_then(|index| match extended.typed_value(index)? {
+                    ExtendedValue::Float(value) => Some(value),
+                    ExtendedValue::Unused | ExtendedValue::Raw(_) => None,
+                })
+        };
+
+        Some(WhiteBalanceInfo {
+            label,
+            red_adjust: adjust(ExtendedDataType::RedAdjust),
+            green_adjust: adjust(ExtendedDataType::GreenAdjust),
+            blue_adjust: adjust(ExtendedDataType::BlueAdjust),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HeaderRef;
+    use crate::test_util::{make_x3f, make_x3f_with_version};
+
+    #[test]
+    fn white_balance_info_reads_label_and_adjustments() {
+        let mut bytes = make_x3f_with_version(0x0002_0001, &[]);
+        let extended = HeaderRef::LENGTH;
+        bytes[extended..extended + 9].copy_from_slice(b"Daylight\0");
+        bytes[extended + 32 + 3] = ExtendedDataType::RedAdjust.as_u8();
+        bytes[extended + 32 + 5] = ExtendedDataType::BlueAdjust.as_u8();
+        let value = extended + 64 + 3 * 4;
+        bytes[value..value + 4].copy_from_slice(&0.25f32.to_le_bytes());
+        let value = extended + 64 + 5 * 4;
+        bytes[value..value + 4].copy_from_slice(&(-0.5f32).to_le_bytes());
+
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        let info = x3f.white_balance_info().expect("extended header");
+        assert_eq!(info.label_str(), Some("Daylight"));
+        assert_eq!(info.red_adjust, Some(0.25));
+        assert_eq!(info.green_adjust, None);
+        assert_eq!(info.blue_adjust, Some(-0.5));
+    }
+
+    #[test]
+    fn white_balance_info_requires_extended_header() {
+        let bytes = make_x3f(&[]);
+        let x3f = X3F::from_bytes(&bytes).expect("valid X3F");
+        assert_eq!(x3f.white_balance_info(), None);
+    }
+}